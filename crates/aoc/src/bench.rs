@@ -0,0 +1,37 @@
+use std::process::ExitCode;
+use aoc_common::{bench, InputOptions};
+use crate::days::{self, Day};
+
+pub fn bench(day: Option<u8>, runs: usize) -> ExitCode {
+    let selected: Vec<&Day> = match day {
+        Some(number) => match days::find(number) {
+            Some(found) => vec![found],
+            None => {
+                eprintln!("day {} is not solved yet", number);
+                return ExitCode::FAILURE
+            }
+        },
+        None => days::DAYS.iter().collect(),
+    };
+
+    let mut timings = Vec::new();
+    let mut failed = false;
+    for day in selected {
+        let timed = day
+            .read_input(&InputOptions::default())
+            .and_then(|input| (day.bench)(&input, runs));
+        match timed {
+            Ok(timed) => timings.push(timed),
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                failed = true;
+            }
+        }
+    }
+    println!("{}", bench::table(&timings));
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,496 @@
+use std::path::PathBuf;
+use aoc_common::{InputOptions, Part};
+use day_1::{
+    diff::DiffOptions,
+    formats::InventoryFormat,
+    rebalance::RebalanceOptions,
+    statistics::StatisticsOptions,
+    subset::Target,
+};
+use day_2::variant::Variant;
+use crate::{
+    client::ClientOptions,
+    guide::{GuideAction, DEFAULT_COUNTER_ORDER, DEFAULT_TOURNAMENT_ROUNDS},
+    inventory::InventoryAction,
+};
+
+pub const USAGE: &str = "usage: aoc run (--all | --day <N> [--part <1|2>] [--input <PATH> | --stdin])
+       aoc verify [--answers <PATH>]
+       aoc bench [--day <N>] [--runs <N>]
+       aoc inventory stats [--percentiles <P,P,..>] [--buckets <N>] [--format <FORMAT>] [--input <PATH> | --stdin]
+       aoc inventory rank [--top <N>] [--csv] [--format <FORMAT>] [--input <PATH> | --stdin]
+       aoc inventory rebalance [--exact-limit <N>] [--format <FORMAT>] [--input <PATH> | --stdin]
+       aoc inventory subset (--exactly <N> | --at-least <N>) [--format <FORMAT>] [--input <PATH> | --stdin]
+       aoc inventory diff <BEFORE> <AFTER> [--by <position|content>] [--top <N>] [--format <FORMAT>]
+       aoc guide score [--rules <PATH>] [--variant <VARIANT>] [--table <PATH>] [--input <PATH> | --stdin]
+       aoc guide infer [--rules <PATH>] [--input <PATH> | --stdin]
+       aoc guide tournament [--bot <BOT>].. [--rounds <N>] [--rules <PATH>] [--variant <VARIANT>] [--input <PATH> | --stdin]
+       aoc guide counter [--order <N>] [--rules <PATH>] [--variant <VARIANT>] [--input <PATH> | --stdin]
+       aoc fetch [--day <N>] [--session <TOKEN>] [--base-url <URL>] [--dir <PATH>]
+       aoc submit --day <N> --part <1|2> [--answer <ANSWER>] [--session <TOKEN>] [--base-url <URL>] [--log <PATH>]
+
+inputs are read from --input, then --stdin, then $AOC_INPUT_DIR/day_N.txt, then the embedded copy
+fetch saves to --dir, then $AOC_INPUT_DIR, then data; the session defaults to $AOC_SESSION
+submit solves the day when no --answer is given, and logs verdicts to $AOC_INPUT_DIR/submissions.tsv
+inventory formats are text, json or csv, detected from the extension or first line when --format is not given
+guide rules files are TOML, or JSON when they end in .json; variants are classic, rpsls, or shape names joined by commas
+bots are constant:<SHAPE>, cycle[:<SHAPE>], random[:<SEED>], frequency, markov or guide; every kind plays when none are given";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Selection),
+    Verify { answers: Option<PathBuf> },
+    Bench { day: Option<u8>, runs: usize },
+    Fetch { day: Option<u8>, client: ClientOptions, dir: Option<PathBuf> },
+    Submit { day: u8, part: Part, answer: Option<String>, client: ClientOptions, log: Option<PathBuf> },
+    Inventory { input: InputOptions, format: Option<InventoryFormat>, action: InventoryAction },
+    Guide { input: InputOptions, action: GuideAction },
+}
+
+pub const DEFAULT_BENCH_RUNS: usize = 20;
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day { day: u8, part: Option<Part>, input: InputOptions },
+}
+
+pub fn parse<I, S>(args: I) -> Result<Command, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+{
+    let args = args.into_iter().map(|arg| arg.as_ref().to_string()).collect::<Vec<_>>();
+    match args.split_first() {
+        Some((command, rest)) if command == "run" => parse_run(rest).map(Command::Run),
+        Some((command, rest)) if command == "verify" => parse_verify(rest),
+        Some((command, rest)) if command == "bench" => parse_bench(rest),
+        Some((command, rest)) if command == "fetch" => parse_fetch(rest),
+        Some((command, rest)) if command == "submit" => parse_submit(rest),
+        Some((command, rest)) if command == "inventory" => parse_inventory(rest),
+        Some((command, rest)) if command == "guide" => parse_guide(rest),
+        Some((command, _)) => Err(format!("unknown command: {}", command)),
+        None => Err("missing command".to_string()),
+    }
+}
+
+fn parse_run(args: &[String]) -> Result<Selection, String> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = InputOptions::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--day" => day = Some(parse_day(value_of(arg, args.next())?)?),
+            "--part" => part = Some(parse_part(value_of(arg, args.next())?)?),
+            "--input" => input.path = Some(PathBuf::from(value_of(arg, args.next())?)),
+            "--stdin" => input.stdin = true,
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    match (all, day) {
+        (true, None) if part.is_none() && input == InputOptions::default() => Ok(Selection::All),
+        (true, _) => Err("--all cannot be combined with --day, --part, --input or --stdin".to_string()),
+        (false, Some(day)) => Ok(Selection::Day { day, part, input }),
+        (false, None) => Err("one of --all or --day is required".to_string()),
+    }
+}
+
+fn parse_verify(args: &[String]) -> Result<Command, String> {
+    let mut answers = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => answers = Some(PathBuf::from(value_of(arg, args.next())?)),
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    Ok(Command::Verify { answers })
+}
+
+fn parse_bench(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut runs = DEFAULT_BENCH_RUNS;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(value_of(arg, args.next())?)?),
+            "--runs" => runs = parse_runs(value_of(arg, args.next())?)?,
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    Ok(Command::Bench { day, runs })
+}
+
+fn parse_fetch(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut client = ClientOptions::default();
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(value_of(arg, args.next())?)?),
+            "--session" => client.session = Some(value_of(arg, args.next())?.to_string()),
+            "--base-url" => client.base_url = Some(value_of(arg, args.next())?.to_string()),
+            "--dir" => dir = Some(PathBuf::from(value_of(arg, args.next())?)),
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    Ok(Command::Fetch { day, client, dir })
+}
+
+fn parse_submit(args: &[String]) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut answer = None;
+    let mut client = ClientOptions::default();
+    let mut log = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => day = Some(parse_day(value_of(arg, args.next())?)?),
+            "--part" => part = Some(parse_part(value_of(arg, args.next())?)?),
+            "--answer" => answer = Some(value_of(arg, args.next())?.to_string()),
+            "--session" => client.session = Some(value_of(arg, args.next())?.to_string()),
+            "--base-url" => client.base_url = Some(value_of(arg, args.next())?.to_string()),
+            "--log" => log = Some(PathBuf::from(value_of(arg, args.next())?)),
+            other => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    let day = day.ok_or("--day is required")?;
+    let part = part.ok_or("--part is required")?;
+    Ok(Command::Submit { day, part, answer, client, log })
+}
+
+fn parse_inventory(args: &[String]) -> Result<Command, String> {
+    let (action, args) = args.split_first().ok_or("missing inventory action")?;
+    let mut input = InputOptions::default();
+    let mut format = None;
+    let mut statistics = StatisticsOptions::default();
+    let mut top = None;
+    let mut csv = false;
+    let mut rebalance = RebalanceOptions::default();
+    let mut target = None;
+    let mut diff = DiffOptions::default();
+    let mut snapshots = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (action.as_str(), arg.as_str()) {
+            (_, "--input") => input.path = Some(PathBuf::from(value_of(arg, args.next())?)),
+            (_, "--stdin") => input.stdin = true,
+            (_, "--format") => format = Some(value_of(arg, args.next())?.parse::<InventoryFormat>()?),
+            ("stats", "--percentiles") => statistics.percentiles = parse_percentiles(value_of(arg, args.next())?)?,
+            ("stats", "--buckets") => statistics.buckets = parse_count(arg, value_of(arg, args.next())?)?,
+            ("rank", "--top") => top = Some(parse_count(arg, value_of(arg, args.next())?)?),
+            ("rank", "--csv") => csv = true,
+            ("rebalance", "--exact-limit") => {
+                let raw = value_of(arg, args.next())?;
+                rebalance.exact_limit = raw.parse().map_err(|_| format!("{} needs a number: {}", arg, raw))?;
+            }
+            ("subset", "--exactly") if target.is_none() => target = Some(Target::Exactly(parse_calories(arg, args.next())?)),
+            ("subset", "--at-least") if target.is_none() => target = Some(Target::AtLeast(parse_calories(arg, args.next())?)),
+            ("diff", "--by") => diff.alignment = value_of(arg, args.next())?.parse()?,
+            ("diff", "--top") => diff.top = parse_count(arg, value_of(arg, args.next())?)?,
+            ("diff", path) if !path.starts_with("--") && snapshots.len() < 2 => snapshots.push(PathBuf::from(path)),
+            (_, other) => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    let action = match action.as_str() {
+        "stats" => InventoryAction::Stats(statistics),
+        "rank" => InventoryAction::Rank { top, csv },
+        "rebalance" => InventoryAction::Rebalance(rebalance),
+        "subset" => InventoryAction::Subset(target.ok_or("subset needs --exactly or --at-least")?),
+        "diff" => {
+            let [before, after] = <[PathBuf; 2]>::try_from(snapshots).map_err(|_| "diff needs two inventory files")?;
+            InventoryAction::Diff { before, after, options: diff }
+        }
+        other => return Err(format!("unknown inventory action: {}", other)),
+    };
+    Ok(Command::Inventory { input, format, action })
+}
+
+fn parse_guide(args: &[String]) -> Result<Command, String> {
+    let (action, args) = args.split_first().ok_or("missing guide action")?;
+    let mut input = InputOptions::default();
+    let mut rules = None;
+    let mut variant = Variant::classic();
+    let mut table = None;
+    let mut bots = Vec::new();
+    let mut rounds = DEFAULT_TOURNAMENT_ROUNDS;
+    let mut order = DEFAULT_COUNTER_ORDER;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (action.as_str(), arg.as_str()) {
+            (_, "--input") => input.path = Some(PathBuf::from(value_of(arg, args.next())?)),
+            (_, "--stdin") => input.stdin = true,
+            ("score" | "infer" | "tournament" | "counter", "--rules") => rules = Some(PathBuf::from(value_of(arg, args.next())?)),
+            ("score" | "tournament" | "counter", "--variant") => variant = value_of(arg, args.next())?.parse()?,
+            ("tournament", "--bot") => bots.push(value_of(arg, args.next())?.to_string()),
+            ("tournament", "--rounds") => rounds = parse_count(arg, value_of(arg, args.next())?)?,
+            ("counter", "--order") => {
+                let raw = value_of(arg, args.next())?;
+                order = raw.parse().map_err(|_| format!("{} needs a number: {}", arg, raw))?;
+            }
+            ("score", "--table") => table = Some(PathBuf::from(value_of(arg, args.next())?)),
+            (_, other) => return Err(format!("unexpected argument: {}", other)),
+        }
+    }
+    let action = match action.as_str() {
+        "score" => GuideAction::Score { rules, variant, table },
+        "infer" => GuideAction::Infer { rules },
+        "tournament" => GuideAction::Tournament { bots, rounds, rules, variant },
+        "counter" => GuideAction::Counter { order, rules, variant },
+        other => return Err(format!("unknown guide action: {}", other)),
+    };
+    Ok(Command::Guide { input, action })
+}
+
+fn parse_calories(flag: &str, value: Option<&String>) -> Result<u64, String> {
+    let raw = value_of(flag, value)?;
+    raw.parse::<u64>()
+        .map_err(|_| format!("{} needs a number of calories: {}", flag, raw))
+}
+
+fn parse_percentiles(raw: &str) -> Result<Vec<f64>, String> {
+    raw.split(',')
+        .map(|p| p.trim().parse::<f64>().map_err(|_| format!("not a valid percentile: {}", p)))
+        .collect()
+}
+
+fn parse_count(flag: &str, raw: &str) -> Result<usize, String> {
+    match raw.parse::<usize>() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("{} needs a positive number: {}", flag, raw)),
+    }
+}
+
+fn value_of<'a>(flag: &str, value: Option<&'a String>) -> Result<&'a str, String> {
+    value
+        .map(String::as_str)
+        .ok_or(format!("{} needs a value", flag))
+}
+
+fn parse_day(raw: &str) -> Result<u8, String> {
+    raw.parse::<u8>()
+        .map_err(|_| format!("not a valid day: {}", raw))
+}
+
+fn parse_runs(raw: &str) -> Result<usize, String> {
+    match raw.parse::<usize>() {
+        Ok(runs) if runs > 0 => Ok(runs),
+        _ => Err(format!("not a valid number of runs: {}", raw)),
+    }
+}
+
+fn parse_part(raw: &str) -> Result<Part, String> {
+    match raw {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("not a valid part: {}", raw)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use day_1::diff::Alignment;
+    use super::*;
+
+    #[test]
+    fn can_parse_single_part() {
+        let expected = Command::Run(Selection::Day { day: 2, part: Some(Part::One), input: InputOptions::default() });
+
+        let actual = parse(["run", "--day", "2", "--part", "1"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_input_path() {
+        let input = InputOptions { path: Some(PathBuf::from("theirs.txt")), stdin: false };
+        let expected = Command::Run(Selection::Day { day: 1, part: None, input });
+
+        let actual = parse(["run", "--day", "1", "--input", "theirs.txt"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_all() {
+        let expected = Command::Run(Selection::All);
+
+        let actual = parse(["run", "--all"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_verify() {
+        let expected = Command::Verify { answers: Some(PathBuf::from("mine.toml")) };
+
+        let actual = parse(["verify", "--answers", "mine.toml"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn bench_defaults_to_every_day() {
+        let expected = Command::Bench { day: None, runs: DEFAULT_BENCH_RUNS };
+
+        let actual = parse(["bench"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn zero_bench_runs_is_rejected() {
+        assert!(parse(["bench", "--runs", "0"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_fetch() {
+        let client = ClientOptions { session: Some("abc".to_string()), base_url: Some("http://localhost:8000".to_string()) };
+        let expected = Command::Fetch { day: Some(4), client, dir: None };
+
+        let actual = parse(["fetch", "--day", "4", "--session", "abc", "--base-url", "http://localhost:8000"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn submit_needs_a_part() {
+        assert!(parse(["submit", "--day", "2", "--answer", "15"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_inventory_stats() {
+        let statistics = StatisticsOptions { percentiles: vec![50.0, 99.5], buckets: 5 };
+        let expected = Command::Inventory { input: InputOptions::default(), format: None, action: InventoryAction::Stats(statistics) };
+
+        let actual = parse(["inventory", "stats", "--percentiles", "50,99.5", "--buckets", "5"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_inventory_rank() {
+        let expected = Command::Inventory {
+            input: InputOptions::default(),
+            format: Some(InventoryFormat::Json),
+            action: InventoryAction::Rank { top: Some(3), csv: true },
+        };
+
+        let actual = parse(["inventory", "rank", "--csv", "--top", "3", "--format", "json"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_inventory_rebalance() {
+        let expected = Command::Inventory {
+            input: InputOptions::default(),
+            format: None,
+            action: InventoryAction::Rebalance(RebalanceOptions { exact_limit: 0 }),
+        };
+
+        let actual = parse(["inventory", "rebalance", "--exact-limit", "0"]).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_inventory_subset() {
+        let expected = Command::Inventory {
+            input: InputOptions::default(),
+            format: None,
+            action: InventoryAction::Subset(Target::AtLeast(200000)),
+        };
+
+        let actual = parse(["inventory", "subset", "--at-least", "200000"]).unwrap();
+
+        assert_eq!(expected, actual);
+        assert!(parse(["inventory", "subset", "--at-least", "1", "--exactly", "2"]).is_err());
+        assert!(parse(["inventory", "subset"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_inventory_diff() {
+        let expected = Command::Inventory {
+            input: InputOptions::default(),
+            format: None,
+            action: InventoryAction::Diff {
+                before: PathBuf::from("old.txt"),
+                after: PathBuf::from("new.txt"),
+                options: DiffOptions { alignment: Alignment::Position, top: 5 },
+            },
+        };
+
+        let actual = parse(["inventory", "diff", "old.txt", "--by", "position", "new.txt", "--top", "5"]).unwrap();
+
+        assert_eq!(expected, actual);
+        assert!(parse(["inventory", "diff", "old.txt"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_guide_score() {
+        let expected = Command::Guide {
+            input: InputOptions::default(),
+            action: GuideAction::Score { rules: Some(PathBuf::from("house.toml")), variant: Variant::rpsls(), table: None },
+        };
+
+        let actual = parse(["guide", "score", "--rules", "house.toml", "--variant", "rpsls"]).unwrap();
+
+        assert_eq!(expected, actual);
+        assert!(parse(["guide", "deal"]).is_err());
+        assert!(parse(["guide", "score", "--variant", "rock,paper"]).is_err());
+        assert!(parse(["guide", "infer", "--variant", "rpsls"]).is_err());
+        assert!(parse(["guide", "infer", "--table", "code.toml"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_guide_tournament() {
+        let expected = Command::Guide {
+            input: InputOptions::default(),
+            action: GuideAction::Tournament {
+                bots: vec!["markov".to_string(), "random:7".to_string()],
+                rounds: 50,
+                rules: None,
+                variant: Variant::classic(),
+            },
+        };
+
+        let actual = parse(["guide", "tournament", "--bot", "markov", "--rounds", "50", "--bot", "random:7"]).unwrap();
+
+        assert_eq!(expected, actual);
+        assert!(parse(["guide", "tournament", "--rounds", "0"]).is_err());
+    }
+
+    #[test]
+    fn can_parse_guide_counter() {
+        let expected = Command::Guide {
+            input: InputOptions::default(),
+            action: GuideAction::Counter { order: 0, rules: None, variant: Variant::classic() },
+        };
+
+        let actual = parse(["guide", "counter", "--order", "0"]).unwrap();
+
+        assert_eq!(expected, actual);
+        assert!(parse(["guide", "counter", "--bot", "markov"]).is_err());
+    }
+
+    #[test]
+    fn rank_options_need_rank() {
+        assert!(parse(["inventory", "stats", "--csv"]).is_err());
+    }
+
+    #[test]
+    fn all_and_day_conflict() {
+        assert!(parse(["run", "--all", "--day", "1"]).is_err());
+    }
+
+    #[test]
+    fn all_and_stdin_conflict() {
+        assert!(parse(["run", "--all", "--stdin"]).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    env,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use aoc_common::{Error, Result};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const YEAR: u16 = 2022;
+pub const LAST_DAY: u8 = 25;
+
+const USER_AGENT: &str = "github.com/MitchTurner/advent2022";
+
+/// Connection settings given on the command line; unset ones fall back to the environment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientOptions {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+/// Talks to the puzzle site, or anything that answers like it, as one logged-in user.
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+        Client {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into(),
+            agent,
+        }
+    }
+
+    pub fn from_options(options: &ClientOptions) -> Result<Self> {
+        let session = options
+            .session
+            .clone()
+            .or_else(|| env::var(SESSION_VAR).ok())
+            .ok_or_else(|| Error::validation(format!("no session token; pass --session or set {}", SESSION_VAR)))?;
+        let base_url = options
+            .base_url
+            .clone()
+            .or_else(|| env::var(BASE_URL_VAR).ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(base_url, session))
+    }
+
+    /// Path of `day`'s puzzle page relative to the base URL.
+    pub fn day_path(day: u8) -> String {
+        format!("/{}/day/{}", YEAR, day)
+    }
+
+    pub fn get(&self, path: &str) -> Result<String> {
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .call();
+        read_body(response)
+    }
+
+    pub fn post_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<String> {
+        let response = self
+            .agent
+            .post(&self.url(path))
+            .set("Cookie", &self.cookie())
+            .send_form(fields);
+        read_body(response)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_body(response: std::result::Result<ureq::Response, ureq::Error>) -> Result<String> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, response)) => Err(Error::Http {
+            status: Some(status),
+            message: response.into_string().unwrap_or_default().trim().to_string(),
+        }),
+        Err(ureq::Error::Transport(transport)) => Err(Error::Http {
+            status: None,
+            message: transport.to_string(),
+        }),
+    }
+}
+
+/// Puzzles unlock at midnight US Eastern, 05:00 UTC, on December `day`.
+pub fn unlocks_at(day: u8) -> Result<SystemTime> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(Error::validation(format!("there is no day {}", day)))
+    }
+    let days = days_from_civil(YEAR as i64, 12, day as i64);
+    let seconds = days * 86_400 + 5 * 3_600;
+    Ok(UNIX_EPOCH + Duration::from_secs(seconds as u64))
+}
+
+/// Refuses days whose puzzle is not out yet at `now`.
+pub fn check_unlocked(day: u8, now: SystemTime) -> Result<()> {
+    let unlocks = unlocks_at(day)?;
+    if now < unlocks {
+        let wait = unlocks.duration_since(now).unwrap_or_default();
+        return Err(Error::validation(format!("day {} unlocks in {}s", day, wait.as_secs())))
+    }
+    Ok(())
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::StubServer;
+
+    #[test]
+    fn day_one_unlocks_at_five_utc() {
+        // 2022-12-01T05:00:00Z
+        let expected = UNIX_EPOCH + Duration::from_secs(1_669_870_800);
+
+        let actual = unlocks_at(1).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn refuses_locked_days() {
+        let just_before = unlocks_at(3).unwrap() - Duration::from_secs(1);
+
+        assert!(check_unlocked(3, just_before).is_err());
+        assert!(check_unlocked(2, just_before).is_ok());
+    }
+
+    #[test]
+    fn there_is_no_day_twenty_six() {
+        assert!(unlocks_at(26).is_err());
+    }
+
+    #[test]
+    fn sends_the_session_cookie() {
+        let server = StubServer::start(vec![(200, "hello")]);
+        let client = Client::new(&server.base_url, "abc123");
+
+        let body = client.get("/2022/day/1/input").unwrap();
+
+        let requests = server.requests();
+        assert_eq!("hello", body);
+        assert!(requests[0].starts_with("GET /2022/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn error_status_is_reported() {
+        let server = StubServer::start(vec![(404, "not yet")]);
+        let client = Client::new(&server.base_url, "abc123");
+
+        let actual = client.get("/2022/day/1/input").unwrap_err();
+
+        server.requests();
+        assert!(matches!(actual, Error::Http { status: Some(404), .. }));
+    }
+}
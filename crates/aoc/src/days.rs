@@ -0,0 +1,52 @@
+use aoc_common::{bench::{self, DayTimings}, solution, Input, InputOptions, Part, Result, Solution};
+use day_1::solution::Day1;
+use day_2::solution::Day2;
+use day_3::solution::Day3;
+
+/// A solved day, with its `Solution` erased so every day can sit in one table.
+pub struct Day {
+    pub number: u8,
+    pub solve: fn(&str, Part) -> Result<String>,
+    pub bench: fn(&str, usize) -> Result<DayTimings>,
+    /// The input compiled into the binary, used when nothing else was asked for.
+    pub embedded: &'static str,
+}
+
+impl Day {
+    const fn of<S: Solution>(embedded: &'static str) -> Self {
+        Day {
+            number: S::DAY,
+            solve: solution::solve::<S>,
+            bench: bench::bench::<S>,
+            embedded,
+        }
+    }
+
+    pub fn read_input(&self, options: &InputOptions) -> Result<String> {
+        Input::resolve(self.number, options, self.embedded)?.read_to_string()
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day::of::<Day1>(day_1::DATA),
+    Day::of::<Day2>(day_2::DATA),
+    Day::of::<Day3>(day_3::DATA),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_in_order_and_unique() {
+        let numbers = DAYS.iter().map(|day| day.number).collect::<Vec<_>>();
+
+        let expected = (1..=DAYS.len() as u8).collect::<Vec<_>>();
+
+        assert_eq!(expected, numbers);
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::SystemTime,
+};
+use aoc_common::{input, Error, Result};
+use crate::client::{self, Client, ClientOptions};
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub fn fetch(days: Vec<u8>, options: ClientOptions, dir: Option<PathBuf>) -> ExitCode {
+    let client = match Client::from_options(&options) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE
+        }
+    };
+    let dir = dir.unwrap_or_else(input::input_dir);
+
+    let mut failed = false;
+    for day in days {
+        match fetch_input(&client, day, &dir, SystemTime::now()) {
+            Ok(Fetched::Cached(path)) => println!("day {}: already cached at {}", day, path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("day {}: saved to {}", day, path.display()),
+            Err(e) => {
+                eprintln!("day {}: {}", day, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Downloads `day`'s input into `dir`, unless it is already there or not unlocked at `now`.
+pub fn fetch_input(client: &Client, day: u8, dir: &Path, now: SystemTime) -> Result<Fetched> {
+    client::check_unlocked(day, now)?;
+    let path = dir.join(input::file_name(day));
+    if path.exists() {
+        return Ok(Fetched::Cached(path))
+    }
+    let body = client.get(&format!("{}/input", Client::day_path(day)))?;
+    if body.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    fs::create_dir_all(dir)?;
+    fs::write(&path, body)?;
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process, time::Duration};
+    use super::*;
+    use crate::stub::StubServer;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn downloads_into_the_cache() {
+        let server = StubServer::start(vec![(200, "A Y\nB X\n")]);
+        let client = Client::new(&server.base_url, "abc123");
+        let dir = scratch_dir("download");
+
+        let actual = fetch_input(&client, 2, &dir, SystemTime::now()).unwrap();
+
+        let requests = server.requests();
+        let path = dir.join("day_2.txt");
+        assert_eq!(Fetched::Downloaded(path.clone()), actual);
+        assert_eq!("A Y\nB X\n", fs::read_to_string(&path).unwrap());
+        assert!(requests[0].starts_with("GET /2022/day/2/input "));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn does_not_refetch_cached_inputs() {
+        let dir = scratch_dir("cached");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day_1.txt"), "1\n").unwrap();
+        // nothing listens here, so any request would fail
+        let client = Client::new("http://127.0.0.1:9", "abc123");
+
+        let actual = fetch_input(&client, 1, &dir, SystemTime::now()).unwrap();
+
+        assert_eq!(Fetched::Cached(dir.join("day_1.txt")), actual);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn refuses_days_not_yet_unlocked() {
+        let dir = scratch_dir("locked");
+        let client = Client::new("http://127.0.0.1:9", "abc123");
+        let before = client::unlocks_at(5).unwrap() - Duration::from_secs(60);
+
+        let actual = fetch_input(&client, 5, &dir, before).unwrap_err();
+
+        assert!(matches!(actual, Error::Validation(_)));
+        assert!(!dir.exists());
+    }
+}
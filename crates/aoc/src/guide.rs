@@ -0,0 +1,114 @@
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+};
+use aoc_common::{Error, Input, InputOptions, Result};
+use day_2::{
+    cipher,
+    counter,
+    file_games::{
+        correct::CorrectFileGames,
+        cyclic::CyclicFileGames,
+        table::TableDecoder,
+        wrong::WrongFileGames,
+        FileGames,
+    },
+    score_keeper::{Game, Games, ScoreKeeper},
+    scoring::ScoringRules,
+    tournament::{self, BotSpec},
+    variant::Variant,
+};
+
+/// Day 2 tools that go beyond the puzzle's two answers.
+#[derive(Debug, PartialEq)]
+pub enum GuideAction {
+    /// Both readings of the guide scored by the puzzle's rules, or by a rules file. Guides for other
+    /// variants are only read as moves, and a mapping table replaces both readings with its own.
+    Score { rules: Option<PathBuf>, variant: Variant, table: Option<PathBuf> },
+    /// Every way of reading the second column as shapes or as outcomes, scored by the puzzle's rules or a rules file.
+    Infer { rules: Option<PathBuf> },
+    /// A round robin between `bots`, or one bot of each kind; the `guide` bot replays the guide as part two reads it,
+    /// or as moves for other variants.
+    Tournament { bots: Vec<String>, rounds: usize, rules: Option<PathBuf>, variant: Variant },
+    /// The best replies to the opponent column given its last `order` shapes, against the guide as part two reads it,
+    /// or as moves for other variants.
+    Counter { order: usize, rules: Option<PathBuf>, variant: Variant },
+}
+
+pub const DEFAULT_TOURNAMENT_ROUNDS: usize = 1000;
+
+pub const DEFAULT_COUNTER_ORDER: usize = 1;
+
+fn load_rules(rules: Option<PathBuf>, variant: &Variant) -> Result<ScoringRules> {
+    match rules {
+        Some(path) => ScoringRules::load_for(variant.clone(), &path),
+        None => Ok(ScoringRules::for_variant(variant.clone())),
+    }
+}
+
+pub fn guide(input: InputOptions, action: GuideAction) -> ExitCode {
+    match run(input, action) {
+        Ok(report) => {
+            print!("{}", report);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// The guide the way its author meant it: as outcomes in the classic game, as moves in the others.
+fn guide_games(input: Input, variant: &Variant) -> Result<Vec<Game>> {
+    if variant == &Variant::classic() {
+        CorrectFileGames::new(input).get_games()
+    } else {
+        CyclicFileGames::new(input, variant.clone()).get_games()
+    }
+}
+
+fn run(input: InputOptions, action: GuideAction) -> Result<String> {
+    let input = Input::resolve(2, &input, day_2::DATA)?;
+    match action {
+        GuideAction::Score { rules, variant, table } => {
+            let rules = load_rules(rules, &variant)?;
+            if let Some(path) = table {
+                let games = FileGames::with_decoder(input, TableDecoder::load(&path, variant)?);
+                let (opp, player) = ScoreKeeper::with_rules(games, rules).total_first()?;
+                return Ok(format!("by the table: {} (opponent {})\n", player, opp))
+            }
+            if variant != Variant::classic() {
+                let (opp, player) = ScoreKeeper::with_rules(CyclicFileGames::new(input, variant), rules).total_first()?;
+                return Ok(format!("as moves: {} (opponent {})\n", player, opp))
+            }
+            let (opp_moves, player_moves) = ScoreKeeper::with_rules(WrongFileGames::new(input.clone()), rules.clone()).total_first()?;
+            let (opp_outcomes, player_outcomes) = ScoreKeeper::with_rules(CorrectFileGames::new(input), rules).total_first()?;
+            Ok(format!(
+                "as moves: {} (opponent {})\nas outcomes: {} (opponent {})\n",
+                player_moves, opp_moves, player_outcomes, opp_outcomes
+            ))
+        }
+        GuideAction::Infer { rules } => {
+            let rules = rules.map_or_else(|| Ok(ScoringRules::default()), |path| ScoringRules::load(&path))?;
+            Ok(cipher::infer(&input, &rules)?.to_string())
+        }
+        GuideAction::Tournament { bots, rounds, rules, variant } => {
+            let rules = load_rules(rules, &variant)?;
+            let guide = guide_games(input, &variant)?;
+            let guide = guide.iter().map(|game| game.player()).collect::<Vec<_>>();
+            let bots = if bots.is_empty() {
+                BotSpec::all(&guide)
+            } else {
+                bots.iter()
+                    .map(|bot| BotSpec::parse(bot, &variant, &guide).map_err(Error::validation))
+                    .collect::<Result<Vec<_>>>()?
+            };
+            Ok(tournament::round_robin(&bots, &rules, rounds)?.to_string())
+        }
+        GuideAction::Counter { order, rules, variant } => {
+            let rules = load_rules(rules, &variant)?;
+            Ok(counter::counter_strategy(&guide_games(input, &variant)?, &rules, order)?.to_string())
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+};
+use aoc_common::{Input, InputOptions, Result};
+use day_1::{
+    diff::{self, DiffOptions},
+    formats::{self, InventoryFormat},
+    ranking::Leaderboard,
+    rebalance::{self, RebalanceOptions},
+    inventory_counter::InventoryCounter,
+    statistics::{InventoryStatistics, StatisticsOptions},
+    subset::Target,
+};
+
+/// Day 1 tools that go beyond the puzzle's two answers.
+#[derive(Debug, PartialEq)]
+pub enum InventoryAction {
+    Stats(StatisticsOptions),
+    /// The leaderboard, cut to the `top` elves when given.
+    Rank { top: Option<usize>, csv: bool },
+    Rebalance(RebalanceOptions),
+    Subset(Target),
+    /// Compares two snapshots, read from files rather than the usual input.
+    Diff { before: PathBuf, after: PathBuf, options: DiffOptions },
+}
+
+pub fn inventory(input: InputOptions, format: Option<InventoryFormat>, action: InventoryAction) -> ExitCode {
+    match run(input, format, action) {
+        Ok(report) => {
+            print!("{}", report);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(input: InputOptions, format: Option<InventoryFormat>, action: InventoryAction) -> Result<String> {
+    let open = || formats::open_inventory(Input::resolve(1, &input, day_1::DATA)?, format);
+    match action {
+        InventoryAction::Stats(options) => Ok(InventoryStatistics::from_reader(&open()?, &options)?.to_string()),
+        InventoryAction::Rank { top, csv } => {
+            let mut leaderboard = Leaderboard::from_reader(&open()?)?;
+            if let Some(top) = top {
+                leaderboard = leaderboard.top(top);
+            }
+            Ok(if csv { leaderboard.to_csv() } else { leaderboard.to_string() })
+        }
+        InventoryAction::Rebalance(options) => Ok(rebalance::rebalance(&open()?, &options)?.to_string()),
+        InventoryAction::Subset(target) => {
+            let subset = InventoryCounter::new(open()?).fewest_elves_holding(target)?;
+            let elves = subset.indices.iter().map(|index| (index + 1).to_string()).collect::<Vec<_>>();
+            Ok(format!("{} elves hold {} calories: {}\n", elves.len(), subset.total, elves.join(", ")))
+        }
+        InventoryAction::Diff { before, after, options } => {
+            let before = formats::open_inventory(Input::File(before), format)?;
+            let after = formats::open_inventory(Input::File(after), format)?;
+            Ok(diff::diff(&before, &after, &options)?.to_string())
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::{
+    env,
+    process::ExitCode,
+};
+use crate::cli::Command;
+
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod days;
+pub mod fetch;
+pub mod guide;
+pub mod inventory;
+pub mod run;
+pub mod submit;
+pub mod verify;
+
+#[cfg(test)]
+mod stub;
+
+fn main() -> ExitCode {
+    let command = match cli::parse(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}\n{}", message, cli::USAGE);
+            return ExitCode::FAILURE
+        }
+    };
+    match command {
+        Command::Run(selection) => run::run(selection),
+        Command::Verify { answers } => verify::verify(answers),
+        Command::Bench { day, runs } => bench::bench(day, runs),
+        Command::Fetch { day, client, dir } => {
+            let days = day.map_or_else(|| days::DAYS.iter().map(|day| day.number).collect(), |day| vec![day]);
+            fetch::fetch(days, client, dir)
+        }
+        Command::Submit { day, part, answer, client, log } => submit::submit(day, part, answer, client, log),
+        Command::Inventory { input, format, action } => inventory::inventory(input, format, action),
+        Command::Guide { input, action } => guide::guide(input, action),
+    }
+}
@@ -0,0 +1,48 @@
+use std::process::ExitCode;
+use aoc_common::{InputOptions, Part};
+use crate::{
+    cli::Selection,
+    days::{self, Day},
+};
+
+pub fn run(selection: Selection) -> ExitCode {
+    let selected: Vec<(&Day, Vec<Part>, InputOptions)> = match selection {
+        Selection::All => days::DAYS
+            .iter()
+            .map(|day| (day, Part::ALL.to_vec(), InputOptions::default()))
+            .collect(),
+        Selection::Day { day, part, input } => match days::find(day) {
+            Some(found) => vec![(found, part.map_or(Part::ALL.to_vec(), |part| vec![part]), input)],
+            None => {
+                eprintln!("day {} is not solved yet", day);
+                return ExitCode::FAILURE
+            }
+        },
+    };
+
+    let mut failed = false;
+    for (day, parts, options) in selected {
+        let input = match day.read_input(&options) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("day {}: {}", day.number, e);
+                failed = true;
+                continue
+            }
+        };
+        for part in parts {
+            match (day.solve)(&input, part) {
+                Ok(answer) => println!("day {} part {}: {}", day.number, part, answer),
+                Err(e) => {
+                    eprintln!("day {} part {}: {}", day.number, part, e);
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
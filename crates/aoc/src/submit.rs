@@ -0,0 +1,322 @@
+use std::{
+    fmt,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+use aoc_common::{input, Error, InputOptions, Part, Result};
+use crate::{
+    client::{Client, ClientOptions},
+    days,
+};
+
+/// Name of the submission log inside the input directory.
+pub const LOG_FILE: &str = "submissions.tsv";
+
+/// What the puzzle site said about a submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// Submitted too recently; try again after the wait, when the site says how long it is.
+    Wait(Option<Duration>),
+    /// The part was already solved, or is not open yet.
+    WrongLevel,
+    Unrecognised,
+}
+
+impl Verdict {
+    pub fn from_response(html: &str) -> Self {
+        if html.contains("That's the right answer") {
+            Verdict::Correct
+        } else if html.contains("You gave an answer too recently") {
+            Verdict::Wait(wait_time(html))
+        } else if html.contains("You don't seem to be solving the right level") {
+            Verdict::WrongLevel
+        } else if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if html.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            }
+        } else {
+            Verdict::Unrecognised
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Wait(_) => "wait",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unrecognised => "unrecognised",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "incorrect" => Some(Verdict::Incorrect),
+            "too-high" => Some(Verdict::TooHigh),
+            "too-low" => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+
+    /// Whether the verdict settles the answer, and so belongs in the log.
+    fn is_final(&self) -> bool {
+        Verdict::from_name(self.name()).is_some()
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Wait(Some(wait)) => write!(f, "wait {}s before submitting again", wait.as_secs()),
+            Verdict::Wait(None) => write!(f, "wait before submitting again"),
+            other => write!(f, "{}", other.name()),
+        }
+    }
+}
+
+// "You have 1m 5s left to wait." or "You have 38s left to wait."
+fn wait_time(html: &str) -> Option<Duration> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+    html[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (number, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let number = number.parse::<u64>().ok()?;
+            match unit {
+                "h" => Some(number * 3_600),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Submission {
+    day: u8,
+    part: Part,
+    answer: String,
+    verdict: Verdict,
+}
+
+/// Every settled answer submitted so far, one tab-separated line each.
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    pub fn load(path: &Path) -> Result<Self> {
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let submissions = raw
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| parse_submission(index + 1, line))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    /// Why `answer` should not be sent, if an earlier verdict already settles it.
+    pub fn known_verdict(&self, day: u8, part: Part, answer: &str) -> Option<Verdict> {
+        let number = answer.parse::<i64>().ok();
+        self.submissions
+            .iter()
+            .filter(|submission| submission.day == day && submission.part == part)
+            .find_map(|submission| {
+                let previous = submission.answer.parse::<i64>().ok();
+                match (&submission.verdict, number, previous) {
+                    (Verdict::Correct, _, _) if submission.answer == answer => Some(Verdict::Correct),
+                    (Verdict::Correct, _, _) => Some(Verdict::Incorrect),
+                    (_, _, _) if submission.answer == answer => Some(submission.verdict.clone()),
+                    (Verdict::TooHigh, Some(number), Some(previous)) if number >= previous => Some(Verdict::TooHigh),
+                    (Verdict::TooLow, Some(number), Some(previous)) if number <= previous => Some(Verdict::TooLow),
+                    _ => None,
+                }
+            })
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, answer: &str, verdict: Verdict) -> Result<()> {
+        if !verdict.is_final() {
+            return Ok(())
+        }
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}\t{}\t{}\t{}", day, part, answer, verdict.name())?;
+        self.submissions.push(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
+        Ok(())
+    }
+}
+
+fn parse_submission(line: usize, raw: &str) -> Result<Submission> {
+    let malformed = || Error::parse(line, 1, format!("expected day, part, answer and verdict: {}", raw));
+    let fields = raw.split('\t').collect::<Vec<_>>();
+    let [day, part, answer, verdict] = fields[..] else {
+        return Err(malformed())
+    };
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => return Err(malformed()),
+    };
+    Ok(Submission {
+        day: day.parse().map_err(|_| malformed())?,
+        part,
+        answer: answer.to_string(),
+        verdict: Verdict::from_name(verdict).ok_or_else(malformed)?,
+    })
+}
+
+/// Posts `answer` for `day` and `part` unless the log already settles it, recording the verdict.
+pub fn submit_answer(client: &Client, log: &mut SubmissionLog, day: u8, part: Part, answer: &str) -> Result<Verdict> {
+    if let Some(verdict) = log.known_verdict(day, part, answer) {
+        return Err(Error::validation(format!("{} was already found to be {}", answer, verdict)))
+    }
+    let level = part.to_string();
+    let html = client.post_form(
+        &format!("{}/answer", Client::day_path(day)),
+        &[("level", level.as_str()), ("answer", answer)],
+    )?;
+    let verdict = Verdict::from_response(&html);
+    log.record(day, part, answer, verdict.clone())?;
+    Ok(verdict)
+}
+
+pub fn submit(day: u8, part: Part, answer: Option<String>, options: ClientOptions, log_path: Option<PathBuf>) -> ExitCode {
+    match try_submit(day, part, answer, &options, log_path) {
+        Ok(Verdict::Correct) => {
+            println!("day {} part {}: correct", day, part);
+            ExitCode::SUCCESS
+        }
+        Ok(verdict) => {
+            println!("day {} part {}: {}", day, part, verdict);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("day {} part {}: {}", day, part, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_submit(day: u8, part: Part, answer: Option<String>, options: &ClientOptions, log_path: Option<PathBuf>) -> Result<Verdict> {
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solved = days::find(day).ok_or_else(|| Error::validation(format!("day {} is not solved yet", day)))?;
+            (solved.solve)(&solved.read_input(&InputOptions::default())?, part)?
+        }
+    };
+    let client = Client::from_options(options)?;
+    let log_path = log_path.unwrap_or_else(|| input::input_dir().join(LOG_FILE));
+    let mut log = SubmissionLog::load(&log_path)?;
+    submit_answer(&client, &mut log, day, part, &answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+    use super::*;
+    use crate::stub::StubServer;
+
+    const RIGHT: &str = "<article><p>That's the right answer! You are one gold star closer.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, \
+        make sure you're using the full input data. Please wait one minute before trying again.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an \
+        answer before trying again.  You have 1m 5s left to wait.</p></article>";
+
+    fn scratch_log(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-submit-{}-{}.tsv", name, process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn can_read_verdicts() {
+        assert_eq!(Verdict::Correct, Verdict::from_response(RIGHT));
+        assert_eq!(Verdict::TooHigh, Verdict::from_response(TOO_HIGH));
+        assert_eq!(Verdict::Wait(Some(Duration::from_secs(65))), Verdict::from_response(TOO_RECENT));
+        assert_eq!(Verdict::Unrecognised, Verdict::from_response("<html></html>"));
+    }
+
+    #[test]
+    fn posts_and_logs_the_answer() {
+        let server = StubServer::start(vec![(200, TOO_HIGH), (200, RIGHT)]);
+        let client = Client::new(&server.base_url, "abc123");
+        let path = scratch_log("posts");
+        let mut log = SubmissionLog::load(&path).unwrap();
+
+        let first = submit_answer(&client, &mut log, 2, Part::One, "900").unwrap();
+        let second = submit_answer(&client, &mut log, 2, Part::One, "15").unwrap();
+
+        let requests = server.requests();
+        assert_eq!(Verdict::TooHigh, first);
+        assert_eq!(Verdict::Correct, second);
+        assert!(requests[0].starts_with("POST /2022/day/2/answer "));
+        assert!(requests[0].ends_with("level=1&answer=900"));
+        assert_eq!("2\t1\t900\ttoo-high\n2\t1\t15\tcorrect\n", fs::read_to_string(&path).unwrap());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn never_resubmits_a_known_wrong_answer() {
+        let path = scratch_log("resubmit");
+        fs::write(&path, "1\t2\t500\ttoo-high\n").unwrap();
+        let mut log = SubmissionLog::load(&path).unwrap();
+        // nothing listens here, so any request would fail
+        let client = Client::new("http://127.0.0.1:9", "abc123");
+
+        let same = submit_answer(&client, &mut log, 1, Part::Two, "500").unwrap_err();
+        let higher = submit_answer(&client, &mut log, 1, Part::Two, "501").unwrap_err();
+
+        assert!(matches!(same, Error::Validation(_)));
+        assert!(matches!(higher, Error::Validation(_)));
+        assert_eq!(None, log.known_verdict(1, Part::Two, "499"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn waiting_is_not_logged() {
+        let server = StubServer::start(vec![(200, TOO_RECENT)]);
+        let client = Client::new(&server.base_url, "abc123");
+        let path = scratch_log("wait");
+        let mut log = SubmissionLog::load(&path).unwrap();
+
+        let verdict = submit_answer(&client, &mut log, 3, Part::One, "7").unwrap();
+
+        server.requests();
+        assert!(matches!(verdict, Verdict::Wait(_)));
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,103 @@
+use std::{
+    path::PathBuf,
+    process::ExitCode,
+};
+use aoc_common::{Answers, InputOptions, Part, Result};
+use crate::days::{self, Day};
+
+/// The checked-in `data/answers.toml`, used unless `--answers` points elsewhere.
+pub const ANSWERS: &str = include_str!("../../../data/answers.toml");
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Correct(String),
+    Wrong { expected: String, actual: String },
+    Unknown(String),
+    Failed(String),
+}
+
+pub fn verify(answers_path: Option<PathBuf>) -> ExitCode {
+    let answers = match answers_path {
+        Some(path) => Answers::load(&path),
+        None => Answers::parse(ANSWERS),
+    };
+    let answers = match answers {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("could not read answers: {}", e);
+            return ExitCode::FAILURE
+        }
+    };
+
+    let mut mismatches = 0;
+    for day in days::DAYS {
+        for (part, outcome) in check_day(day, &answers) {
+            match outcome {
+                Outcome::Correct(actual) => println!("day {} part {}: ok ({})", day.number, part, actual),
+                Outcome::Unknown(actual) => println!("day {} part {}: no known answer ({})", day.number, part, actual),
+                Outcome::Wrong { expected, actual } => {
+                    mismatches += 1;
+                    println!("day {} part {}: MISMATCH expected {}, got {}", day.number, part, expected, actual);
+                }
+                Outcome::Failed(message) => {
+                    mismatches += 1;
+                    println!("day {} part {}: FAILED {}", day.number, part, message);
+                }
+            }
+        }
+    }
+    if mismatches > 0 {
+        eprintln!("{} answer(s) did not verify", mismatches);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Reruns both parts of `day` on its personal input and compares them with `answers`.
+pub fn check_day(day: &Day, answers: &Answers) -> Vec<(Part, Outcome)> {
+    let input = day.read_input(&InputOptions::default());
+    Part::ALL
+        .iter()
+        .map(|part| {
+            let actual = input.as_ref()
+                .map_err(|e| e.to_string())
+                .and_then(|input| (day.solve)(input, *part).map_err(|e| e.to_string()));
+            (*part, outcome(actual, answers.get(day.number, *part)))
+        })
+        .collect()
+}
+
+fn outcome(actual: Result<String, String>, expected: Option<&str>) -> Outcome {
+    match (actual, expected) {
+        (Err(message), _) => Outcome::Failed(message),
+        (Ok(actual), None) => Outcome::Unknown(actual),
+        (Ok(actual), Some(expected)) if actual == expected => Outcome::Correct(actual),
+        (Ok(actual), Some(expected)) => Outcome::Wrong { expected: expected.to_string(), actual },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_answer_is_a_mismatch() {
+        let expected = Outcome::Wrong { expected: "10".to_string(), actual: "11".to_string() };
+
+        let actual = outcome(Ok("11".to_string()), Some("10"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn checked_in_answers_verify() {
+        let answers = Answers::parse(ANSWERS).unwrap();
+
+        for day in days::DAYS {
+            for (part, outcome) in check_day(day, &answers) {
+                assert!(matches!(outcome, Outcome::Correct(_)), "day {} part {}: {:?}", day.number, part, outcome);
+            }
+        }
+    }
+}
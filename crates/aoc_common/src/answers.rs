@@ -0,0 +1,111 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+};
+use crate::{sections, Error, Part, Result};
+
+/// Known answers for one day's parts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl DayAnswers {
+    /// Parses `part_N = answer` lines; answers may be bare or quoted.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut answers = DayAnswers::default();
+        for entry in sections::entries(raw)? {
+            if let Some(section) = entry.section {
+                return Err(Error::parse(entry.line, 1, format!("one day's answers have no sections: [{}]", section)))
+            }
+            answers.set(entry.line, entry.key, entry.value)?;
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref(),
+        }
+    }
+
+    fn set(&mut self, line: usize, key: &str, value: &str) -> Result<()> {
+        let value = Some(value.to_string());
+        match key {
+            "part_1" => self.part_one = value,
+            "part_2" => self.part_two = value,
+            other => return Err(Error::parse(line, 1, format!("unknown part: {}", other))),
+        }
+        Ok(())
+    }
+}
+
+/// Known answers for every solved day, read from a `[day_N]`-sectioned file like `data/answers.toml`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    days: BTreeMap<u8, DayAnswers>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        Answers::parse(&fs::read_to_string(path)?)
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut days = BTreeMap::new();
+        for entry in sections::entries(raw)? {
+            let section = entry.section.ok_or_else(|| Error::parse(entry.line, 1, "answer outside of a [day_N] section"))?;
+            let number = section
+                .strip_prefix("day_")
+                .and_then(|number| number.parse::<u8>().ok())
+                .ok_or_else(|| Error::parse(entry.line, 1, format!("answer under [{}], not [day_N]", section)))?;
+            days.entry(number)
+                .or_insert_with(DayAnswers::default)
+                .set(entry.line, entry.key, entry.value)?;
+        }
+        Ok(Answers { days })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.days.get(&day).and_then(|answers| answers.get(part))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_day_answers() {
+        let raw = "# from the puzzle text\npart_1 = 24000\n\npart_2 = \"45000\"\n";
+
+        let expected = DayAnswers {
+            part_one: Some("24000".to_string()),
+            part_two: Some("45000".to_string()),
+        };
+        let actual = DayAnswers::parse(raw).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_parse_sections() {
+        let raw = "[day_1]\npart_1 = \"1\"\n\n[day_3]\npart_2 = \"32\"\n";
+
+        let answers = Answers::parse(raw).unwrap();
+
+        assert_eq!(Some("1"), answers.get(1, Part::One));
+        assert_eq!(None, answers.get(1, Part::Two));
+        assert_eq!(Some("32"), answers.get(3, Part::Two));
+    }
+
+    #[test]
+    fn answer_needs_a_section() {
+        let actual = Answers::parse("part_1 = 3").unwrap_err();
+
+        assert!(matches!(actual, Error::Parse { line: 1, .. }));
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    hint,
+    time::{Duration, Instant},
+};
+use crate::{Error, Result, Solution};
+
+/// Summary of repeated timings of one phase.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timing {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Timing {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Result<Self> {
+        if samples.is_empty() {
+            return Err(Error::validation("cannot summarise zero benchmark runs"))
+        }
+        samples.sort();
+        let middle = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[middle - 1] + samples[middle]) / 2
+        } else {
+            samples[middle]
+        };
+        Ok(Timing {
+            runs: samples.len(),
+            min: samples[0],
+            median,
+        })
+    }
+}
+
+/// Parse and solve timings of one day, measured separately.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayTimings {
+    pub day: u8,
+    pub parse: Timing,
+    pub part_one: Timing,
+    pub part_two: Timing,
+}
+
+/// Times `S::parse` and each part of `S` over `runs` repetitions on `input`.
+pub fn bench<S: Solution>(input: &str, runs: usize) -> Result<DayTimings> {
+    let parse = time(runs, || S::parse(input))?;
+    let parsed = S::parse(input)?;
+    let part_one = time(runs, || S::part_one(&parsed))?;
+    let part_two = time(runs, || S::part_two(&parsed))?;
+    Ok(DayTimings {
+        day: S::DAY,
+        parse,
+        part_one,
+        part_two,
+    })
+}
+
+fn time<T, F>(runs: usize, mut f: F) -> Result<Timing>
+    where
+        F: FnMut() -> Result<T>,
+{
+    let samples = (0..runs)
+        .map(|_| {
+            let start = Instant::now();
+            let output = f()?;
+            let elapsed = start.elapsed();
+            hint::black_box(output);
+            Ok(elapsed)
+        })
+        .collect::<Result<Vec<Duration>>>()?;
+    Timing::from_samples(samples)
+}
+
+/// Renders `timings` as an aligned table with one row per phase.
+pub fn table(timings: &[DayTimings]) -> String {
+    let mut rows = vec![[
+        "day".to_string(),
+        "phase".to_string(),
+        "runs".to_string(),
+        "min".to_string(),
+        "median".to_string(),
+    ]];
+    for day in timings {
+        let phases = [("parse", &day.parse), ("part 1", &day.part_one), ("part 2", &day.part_two)];
+        for (phase, timing) in phases {
+            rows.push([
+                day.day.to_string(),
+                phase.to_string(),
+                timing.runs.to_string(),
+                format!("{:.2?}", timing.min),
+                format!("{:.2?}", timing.median),
+            ]);
+        }
+    }
+    let mut widths = [0; 5];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn can_summarise_odd_runs() {
+        let expected = Timing {
+            runs: 3,
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(4),
+        };
+
+        let actual = Timing::from_samples(millis(&[9, 1, 4])).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn even_runs_average_the_middle() {
+        let actual = Timing::from_samples(millis(&[2, 8, 4, 6])).unwrap();
+
+        assert_eq!(Duration::from_millis(5), actual.median);
+    }
+
+    #[test]
+    fn zero_runs_is_an_error() {
+        assert!(Timing::from_samples(Vec::new()).is_err());
+    }
+}
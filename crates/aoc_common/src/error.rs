@@ -0,0 +1,116 @@
+use std::{
+    error,
+    fmt,
+    io,
+};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input could not be read.
+    Io(io::Error),
+    /// A piece of the input could not be understood. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input was well-formed but breaks one of the puzzle's rules.
+    Validation(String),
+    /// The input held nothing to solve.
+    EmptyInput,
+    /// A running total grew past what its integer type can hold.
+    Overflow(String),
+    /// A search ran out of its step budget before it could settle the answer either way.
+    SearchExhausted(String),
+    /// A request to the puzzle site failed; `status` is set when the server answered.
+    Http {
+        status: Option<u16>,
+        message: String,
+    },
+}
+
+impl Error {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Error::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Error::Validation(message.into())
+    }
+
+    pub fn overflow(message: impl Into<String>) -> Self {
+        Error::Overflow(message.into())
+    }
+
+    pub fn search_exhausted(message: impl Into<String>) -> Self {
+        Error::SearchExhausted(message.into())
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "parse error at {}:{}: {}", line, column, message),
+            Error::Validation(message) => write!(f, "invalid input: {}", message),
+            Error::EmptyInput => write!(f, "input is empty"),
+            Error::Overflow(message) => write!(f, "overflow: {}", message),
+            Error::SearchExhausted(message) => write!(f, "search budget exhausted: {}", message),
+            Error::Http {
+                status: Some(status),
+                message,
+            } => write!(f, "http error {}: {}", status, message),
+            Error::Http {
+                status: None,
+                message,
+            } => write!(f, "http error: {}", message),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_displays_position() {
+        let error = Error::parse(3, 5, "Not a valid move: Q");
+
+        let expected = "parse error at 3:5: Not a valid move: Q";
+        let actual = error.to_string();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn io_error_is_the_source() {
+        let error: Error = io::Error::new(io::ErrorKind::NotFound, "gone").into();
+
+        assert!(error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use crate::{answers::DayAnswers, Error, Part, Result};
+
+/// `data/examples`, where each day keeps `day_N/<name>.txt` next to `day_N/<name>.answers`.
+pub fn examples_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../../data/examples")
+}
+
+/// One example input and the answers it is known to produce.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: PathBuf,
+    pub answers: DayAnswers,
+}
+
+/// Every example stored for `day` under `dir`, sorted by name.
+pub fn discover(dir: &Path, day: u8) -> Result<Vec<Example>> {
+    let day_dir = dir.join(format!("day_{}", day));
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(&day_dir)? {
+        let input = entry?.path();
+        if input.extension().and_then(|ext| ext.to_str()) != Some("txt") {
+            continue
+        }
+        let name = input
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| Error::validation(format!("example has no name: {}", input.display())))?
+            .to_string();
+        let answers = DayAnswers::parse(&fs::read_to_string(input.with_extension("answers"))?)?;
+        examples.push(Example { name, input, answers });
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Runs `solve` over every example for `day`, panicking with a summary of any wrong answers.
+pub fn check_examples<F>(day: u8, solve: F)
+    where
+        F: Fn(&Path, Part) -> Result<String>,
+{
+    let examples = discover(&examples_dir(), day).unwrap();
+    assert!(!examples.is_empty(), "no examples found for day {}", day);
+
+    let failures = examples
+        .iter()
+        .flat_map(|example| Part::ALL.iter().map(move |part| (example, *part)))
+        .filter_map(|(example, part)| {
+            let expected = example.answers.get(part)?;
+            match solve(&example.input, part) {
+                Ok(actual) if actual == expected => None,
+                Ok(actual) => Some(format!("{} part {}: expected {}, got {}", example.name, part, expected, actual)),
+                Err(e) => Some(format!("{} part {}: {}", example.name, part, e)),
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(failures.is_empty(), "day {} examples failed:\n{}", day, failures.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_checked_in_examples() {
+        let examples = discover(&examples_dir(), 2).unwrap();
+
+        assert!(examples.iter().any(|example| example.name == "puzzle"));
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    env,
+    ffi::OsString,
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+};
+use crate::Result;
+
+/// Directory holding `day_N.txt` inputs, consulted when no path or stdin was asked for.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Where fetched inputs are cached when `AOC_INPUT_DIR` is not set.
+pub const DEFAULT_INPUT_DIR: &str = "data";
+
+/// Where a day's puzzle input comes from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin(String),
+    Embedded(&'static str),
+}
+
+/// What the caller asked for explicitly; anything left unset falls through to the environment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputOptions {
+    pub path: Option<PathBuf>,
+    pub stdin: bool,
+}
+
+impl Input {
+    /// Picks the input for `day`: an explicit path, then stdin, then `AOC_INPUT_DIR`, then `embedded`.
+    pub fn resolve(day: u8, options: &InputOptions, embedded: &'static str) -> Result<Self> {
+        resolve_with(day, options, env::var_os(INPUT_DIR_VAR), embedded, || {
+            let mut buffer = String::new();
+            io::stdin().read_to_string(&mut buffer)?;
+            Ok(buffer)
+        })
+    }
+
+    pub fn read_to_string(&self) -> Result<String> {
+        match self {
+            Input::File(path) => Ok(fs::read_to_string(path)?),
+            Input::Stdin(text) => Ok(text.clone()),
+            Input::Embedded(text) => Ok(text.to_string()),
+        }
+    }
+
+    pub fn lines(&self) -> Result<Box<dyn Iterator<Item = io::Result<String>> + '_>> {
+        match self {
+            Input::File(path) => {
+                let file = File::open(path)?;
+                Ok(Box::new(BufReader::new(file).lines()))
+            }
+            Input::Stdin(text) => Ok(Box::new(text.lines().map(|line| Ok(line.to_string())))),
+            Input::Embedded(text) => Ok(Box::new(text.lines().map(|line| Ok(line.to_string())))),
+        }
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(path: PathBuf) -> Self {
+        Input::File(path)
+    }
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Stdin(_) => write!(f, "stdin"),
+            Input::Embedded(_) => write!(f, "embedded input"),
+        }
+    }
+}
+
+/// The directory inputs are cached in: `AOC_INPUT_DIR`, or `data` when unset.
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+/// File name of a day's input inside an input directory.
+pub fn file_name(day: u8) -> String {
+    format!("day_{}.txt", day)
+}
+
+fn resolve_with<F>(day: u8, options: &InputOptions, input_dir: Option<OsString>, embedded: &'static str, read_stdin: F) -> Result<Input>
+    where
+        F: FnOnce() -> io::Result<String>,
+{
+    if let Some(path) = &options.path {
+        return Ok(Input::File(path.clone()))
+    }
+    if options.stdin {
+        return Ok(Input::Stdin(read_stdin()?))
+    }
+    if let Some(dir) = input_dir {
+        return Ok(Input::File(PathBuf::from(dir).join(file_name(day))))
+    }
+    Ok(Input::Embedded(embedded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_stdin() -> io::Result<String> {
+        panic!("stdin should not be read")
+    }
+
+    #[test]
+    fn explicit_path_wins() {
+        let options = InputOptions {
+            path: Some(PathBuf::from("mine.txt")),
+            stdin: true,
+        };
+
+        let actual = resolve_with(1, &options, Some("dir".into()), "", no_stdin).unwrap();
+
+        assert_eq!(Input::File(PathBuf::from("mine.txt")), actual);
+    }
+
+    #[test]
+    fn stdin_beats_input_dir() {
+        let options = InputOptions {
+            path: None,
+            stdin: true,
+        };
+
+        let actual = resolve_with(1, &options, Some("dir".into()), "", || Ok("1\n".to_string())).unwrap();
+
+        assert_eq!(Input::Stdin("1\n".to_string()), actual);
+    }
+
+    #[test]
+    fn input_dir_beats_embedded() {
+        let actual = resolve_with(2, &InputOptions::default(), Some("dir".into()), "", no_stdin).unwrap();
+
+        assert_eq!(Input::File(PathBuf::from("dir").join("day_2.txt")), actual);
+    }
+
+    #[test]
+    fn falls_back_to_embedded() {
+        let actual = resolve_with(3, &InputOptions::default(), None, "abc", no_stdin).unwrap();
+
+        assert_eq!(Input::Embedded("abc"), actual);
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+use crate::error::{Error, Result};
+
+/// Just enough JSON for arrays and objects of whole numbers, keeping the 1-based line and column for errors.
+///
+/// Strings have no escapes, and numbers are read as whatever the caller asks for.
+pub struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(raw: &'a str) -> Self {
+        Cursor { chars: raw.chars().peekable(), line: 1, column: 1 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Calls `element` with the cursor at the start of each element.
+    pub fn array(&mut self, mut element: impl FnMut(&mut Self) -> Result<()>) -> Result<()> {
+        self.expect('[')?;
+        if self.accept(']') {
+            return Ok(())
+        }
+        loop {
+            self.skip_whitespace();
+            element(self)?;
+            if self.accept(']') {
+                return Ok(())
+            }
+            self.expect(',')?;
+        }
+    }
+
+    /// Calls `member` with each key, leaving the cursor at its value.
+    pub fn object(&mut self, mut member: impl FnMut(&mut Self, &str) -> Result<()>) -> Result<()> {
+        self.expect('{')?;
+        if self.accept('}') {
+            return Ok(())
+        }
+        loop {
+            let key = self.string()?;
+            self.expect(':')?;
+            self.skip_whitespace();
+            member(self, &key)?;
+            if self.accept('}') {
+                return Ok(())
+            }
+            self.expect(',')?;
+        }
+    }
+
+    pub fn string(&mut self) -> Result<String> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.chars.peek().copied() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(text)
+                }
+                Some('\n') | None => return Err(self.error("unterminated string")),
+                Some(c) => {
+                    text.push(c);
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    /// Reads a number as a `T`; `what` names it in the error when it does not parse.
+    pub fn number<T: FromStr>(&mut self, what: &str) -> Result<T> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+        let mut digits = String::new();
+        while let Some(c) = self.chars.peek().copied().filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+')) {
+            digits.push(c);
+            self.bump();
+        }
+        digits
+            .parse::<T>()
+            .map_err(|_| Error::parse(line, column, format!("not {}: {:?}", what, digits)))
+    }
+
+    /// Errors unless only whitespace is left after the `what` just read.
+    pub fn end(&mut self, what: &str) -> Result<()> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            Some(c) => Err(self.error(format!("unexpected {:?} after the {}", c, what))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn accept(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        if self.chars.peek() == Some(&expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, expected: char) -> Result<()> {
+        if self.accept(expected) {
+            return Ok(())
+        }
+        let found = match self.chars.peek() {
+            Some(c) => format!("{:?}", c),
+            None => "the end of the input".to_string(),
+        };
+        Err(self.error(format!("expected {:?}, found {}", expected, found)))
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        Error::parse(self.line, self.column, message)
+    }
+
+    fn bump(&mut self) {
+        if self.chars.next() == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_nested_values() {
+        // given
+        let mut cursor = Cursor::new("{\"a\": [1, 2],\n \"b\": []}");
+        let mut read = Vec::new();
+
+        // when
+        cursor
+            .object(|cursor, key| {
+                let key = key.to_string();
+                cursor.array(|cursor| {
+                    read.push((key.clone(), cursor.line(), cursor.number::<u32>("a count")?));
+                    Ok(())
+                })
+            })
+            .unwrap();
+
+        // then
+        assert_eq!(vec![("a".to_string(), 1, 1), ("a".to_string(), 1, 2)], read);
+        assert!(cursor.end("object").is_ok());
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_character() {
+        let mut cursor = Cursor::new("[1,\n  x]");
+
+        let actual = cursor.array(|cursor| cursor.number::<u32>("a count").map(drop)).unwrap_err();
+
+        assert!(matches!(actual, Error::Parse { line: 2, column: 3, .. }));
+    }
+}
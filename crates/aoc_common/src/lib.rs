@@ -0,0 +1,13 @@
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod examples;
+pub mod input;
+pub mod json;
+pub mod sections;
+pub mod solution;
+
+pub use answers::Answers;
+pub use error::{Error, Result};
+pub use input::{Input, InputOptions};
+pub use solution::{Part, Solution};
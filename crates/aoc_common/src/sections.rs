@@ -0,0 +1,102 @@
+use crate::error::{Error, Result};
+
+/// One `key = value` line of a sectioned file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// 1-based.
+    pub line: usize,
+    /// The last `[section]` above the line, if there was one.
+    pub section: Option<&'a str>,
+    pub key: &'a str,
+    pub value: &'a str,
+    /// Where the value starts, 1-based, for errors about it.
+    pub value_column: usize,
+}
+
+/// Reads the little TOML the config files need: `[section]` headers and `key = value` lines.
+///
+/// Keys and values may be quoted. Blank lines are skipped, and so is anything from a `#` outside quotes,
+/// whether it starts the line or follows a value.
+pub fn entries(raw: &str) -> Result<Vec<Entry<'_>>> {
+    let mut entries = Vec::new();
+    let mut section = None;
+    for (index, line) in raw.lines().enumerate() {
+        let content = without_comment(line);
+        let trimmed = content.trim();
+        if trimmed.is_empty() {
+            continue
+        }
+        if trimmed.starts_with('[') {
+            let name = trimmed
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .ok_or_else(|| Error::parse(index + 1, 1, format!("expected `[section]`: {}", trimmed)))?;
+            section = Some(name.trim());
+            continue
+        }
+        let (key, value) = content
+            .split_once('=')
+            .ok_or_else(|| Error::parse(index + 1, 1, format!("expected `key = value`: {}", trimmed)))?;
+        let value_offset = content.len() - value.trim_start().len();
+        let quoted = value.trim().starts_with('"');
+        entries.push(Entry {
+            line: index + 1,
+            section,
+            key: unquote(key).map_err(|message| Error::parse(index + 1, 1, message))?,
+            value: unquote(value).map_err(|message| Error::parse(index + 1, value_offset + 1, message))?,
+            value_column: value_offset + 1 + quoted as usize,
+        });
+    }
+    Ok(entries)
+}
+
+fn without_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (offset, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..offset],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn unquote(s: &str) -> Result<&str, String> {
+    let s = s.trim();
+    match s.strip_prefix('"') {
+        Some(rest) => rest.strip_suffix('"').ok_or_else(|| format!("unterminated string: {}", s)),
+        None => Ok(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_sections_and_comments() {
+        // given
+        let raw = "# top\ntop = 1\n\n[first]\n\"a\" = \"x # y\"  # why\n  b =  2#two\n";
+
+        // when
+        let actual = entries(raw).unwrap();
+
+        // then
+        let expected = vec![
+            Entry { line: 2, section: None, key: "top", value: "1", value_column: 7 },
+            Entry { line: 5, section: Some("first"), key: "a", value: "x # y", value_column: 8 },
+            Entry { line: 6, section: Some("first"), key: "b", value: "2", value_column: 8 },
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn malformed_lines_report_their_position() {
+        let missing = entries("[a]\njust words\n").unwrap_err();
+        let unterminated = entries("a = \"open\n").unwrap_err();
+
+        assert!(matches!(missing, Error::Parse { line: 2, column: 1, .. }));
+        assert!(matches!(unterminated, Error::Parse { line: 1, column: 5, .. }));
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+use crate::Result;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// A single day's puzzle: how to parse its input and how to answer each part.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Result<String>;
+    fn part_two(input: &Self::Input) -> Result<String>;
+}
+
+/// Parses `input` and answers the requested `part` of solution `S`.
+pub fn solve<S: Solution>(input: &str, part: Part) -> Result<String> {
+    let input = S::parse(input)?;
+    match part {
+        Part::One => S::part_one(&input),
+        Part::Two => S::part_two(&input),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct LineCount;
+
+    impl Solution for LineCount {
+        const DAY: u8 = 0;
+
+        type Input = Vec<String>;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.lines().map(str::to_string).collect())
+        }
+
+        fn part_one(input: &Self::Input) -> Result<String> {
+            Ok(input.len().to_string())
+        }
+
+        fn part_two(input: &Self::Input) -> Result<String> {
+            Ok(input.concat())
+        }
+    }
+
+    #[test]
+    fn solve_dispatches_on_part() {
+        let input = "ab\ncd\n";
+
+        assert_eq!("2", solve::<LineCount>(input, Part::One).unwrap());
+        assert_eq!("abcd", solve::<LineCount>(input, Part::Two).unwrap());
+    }
+}
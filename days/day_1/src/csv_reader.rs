@@ -0,0 +1,115 @@
+use std::{
+    collections::HashMap,
+    io,
+};
+use aoc_common::{Error, Input, Result};
+use crate::inventory_counter::{Elf, Reader};
+
+/// Reads an inventory exported as CSV rows of `elf_id,calories`, one row per item.
+///
+/// Elves are numbered in the order their id first appears, and rows for one elf need not be adjacent.
+/// A header is skipped when the first row's calories column is not a number.
+pub struct CsvReader {
+    input: Input,
+}
+
+impl CsvReader {
+    pub fn new(input: Input) -> Self {
+        CsvReader { input }
+    }
+}
+
+impl Reader for CsvReader {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        Ok(self.read_elves()?.into_iter().map(|elf| elf.items).collect())
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        elves_from_csv(self.input.lines()?)
+    }
+}
+
+/// Each elf's `lines` run from its first row to its last.
+pub(crate) fn elves_from_csv<I>(lines: I) -> Result<Vec<Elf>>
+    where
+        I: Iterator<Item = io::Result<String>>,
+{
+    let mut elves: Vec<Elf> = Vec::new();
+    let mut positions = HashMap::new();
+    let mut seen_row = false;
+
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue
+        }
+        let first_row = !seen_row;
+        seen_row = true;
+
+        let Some((id, calories)) = line.split_once(',').filter(|(_, calories)| !calories.contains(',')) else {
+            return Err(Error::parse(line_number, 1, format!("expected elf_id,calories: {:?}", line)))
+        };
+        let calories = match calories.trim().parse::<u32>() {
+            Ok(calories) => calories,
+            Err(_) if first_row => continue,
+            Err(_) => return Err(Error::parse(
+                line_number,
+                id.len() + 2,
+                format!("not a calorie count: {:?}", calories.trim()),
+            )),
+        };
+
+        let position = *positions.entry(id.trim().to_string()).or_insert_with(|| {
+            elves.push(Elf { id: elves.len() + 1, lines: line_number..line_number + 1, items: Vec::new() });
+            elves.len() - 1
+        });
+        let elf = &mut elves[position];
+        elf.lines.end = line_number + 1;
+        elf.items.push(calories);
+    }
+    if elves.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(raw: &str) -> impl Iterator<Item = io::Result<String>> + '_ {
+        raw.lines().map(|line| Ok(line.to_string()))
+    }
+
+    #[test]
+    fn can_group_rows_by_elf() {
+        // given
+        let raw = "elf_id,calories\nalice,1000\nbob,4000\nalice,2000\n\ncarol, 3000\n";
+
+        // when
+        let actual = elves_from_csv(lines(raw)).unwrap();
+
+        // then
+        let expected = vec![
+            Elf { id: 1, lines: 2..5, items: vec![1000, 2000] },
+            Elf { id: 2, lines: 3..4, items: vec![4000] },
+            Elf { id: 3, lines: 6..7, items: vec![3000] },
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn reports_bad_calories_after_the_header() {
+        let actual = elves_from_csv(lines("7,100\n8,lots\n")).unwrap_err();
+
+        assert!(matches!(actual, Error::Parse { line: 2, column: 3, .. }));
+    }
+
+    #[test]
+    fn header_alone_is_empty() {
+        let actual = elves_from_csv(lines("elf_id,calories\n")).unwrap_err();
+
+        assert!(matches!(actual, Error::EmptyInput));
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+use aoc_common::Result;
+use crate::{
+    inventory_counter::{Elf, Reader},
+    multiset,
+    ranking::Leaderboard,
+};
+
+/// How alike two elves' items must be before content alignment treats them as the same elf.
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Cells in the content alignment table, changed elves before times after, past which elves are paired by position.
+const TABLE_LIMIT: u128 = 1 << 24;
+
+/// Paired 0-based positions before and after; `None` where the elf is missing from that snapshot.
+type Pairs = Vec<(Option<usize>, Option<usize>)>;
+
+/// How elves in one snapshot are paired with elves in the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// The nth elf before is the nth elf after.
+    Position,
+    /// Elves are paired in order by how many items they share, so insertions and removals line up.
+    #[default]
+    Content,
+}
+
+impl FromStr for Alignment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "position" => Ok(Alignment::Position),
+            "content" => Ok(Alignment::Content),
+            other => Err(format!("unknown alignment: {}", other)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DiffOptions {
+    pub alignment: Alignment,
+    /// How much of the leaderboard to compare.
+    pub top: usize,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        DiffOptions { alignment: Alignment::default(), top: 3 }
+    }
+}
+
+/// One elf that differs between snapshots; elves are identified by their 1-based ids in each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ElfChange {
+    Added { after: usize, items: Vec<u32> },
+    Removed { before: usize, items: Vec<u32> },
+    /// `lost` items are only in the earlier snapshot and `gained` ones only in the later.
+    Changed { before: usize, after: usize, lost: Vec<u32>, gained: Vec<u32> },
+}
+
+/// An elf's place on one snapshot's leaderboard.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Standing {
+    pub elf: usize,
+    pub rank: usize,
+}
+
+/// One elf near the top of either leaderboard; `None` where it is missing from that snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RankChange {
+    pub before: Option<Standing>,
+    pub after: Option<Standing>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InventoryDiff {
+    pub elves_before: usize,
+    pub elves_after: usize,
+    pub unchanged: usize,
+    pub changes: Vec<ElfChange>,
+    pub top: usize,
+    pub leaderboard: Vec<RankChange>,
+    /// Why the elves were not aligned as asked, when they were not.
+    pub note: Option<String>,
+}
+
+pub fn diff<A: Reader, B: Reader>(before: &A, after: &B, options: &DiffOptions) -> Result<InventoryDiff> {
+    let old = before.read_elves()?;
+    let new = after.read_elves()?;
+    let mut note = None;
+    let pairs = match options.alignment {
+        Alignment::Position => by_position(old.len(), new.len()),
+        Alignment::Content => by_content(&old, &new).unwrap_or_else(|(n, m)| {
+            note = Some(format!("{} by {} changed elves are too many to align by content, so they were aligned by position", n, m));
+            by_position(old.len(), new.len())
+        }),
+    };
+
+    let mut unchanged = 0;
+    let mut changes = Vec::new();
+    for pair in &pairs {
+        match *pair {
+            (Some(i), Some(j)) => {
+                let (was, is) = (multiset::sorted(&old[i].items), multiset::sorted(&new[j].items));
+                if was == is {
+                    unchanged += 1;
+                } else {
+                    changes.push(ElfChange::Changed {
+                        before: old[i].id,
+                        after: new[j].id,
+                        lost: multiset::difference(&was, &is),
+                        gained: multiset::difference(&is, &was),
+                    });
+                }
+            }
+            (Some(i), None) => changes.push(ElfChange::Removed { before: old[i].id, items: old[i].items.clone() }),
+            (None, Some(j)) => changes.push(ElfChange::Added { after: new[j].id, items: new[j].items.clone() }),
+            (None, None) => {}
+        }
+    }
+
+    let (elves_before, elves_after) = (old.len(), new.len());
+    let ranks_before = ranks(Leaderboard::from_elves(old)?);
+    let ranks_after = ranks(Leaderboard::from_elves(new)?);
+    let standing = |ranks: &[usize], index: Option<usize>| index.map(|index| Standing { elf: index + 1, rank: ranks[index] });
+    let mut leaderboard = pairs
+        .into_iter()
+        .map(|(i, j)| RankChange { before: standing(&ranks_before, i), after: standing(&ranks_after, j) })
+        .filter(|change| {
+            [change.before, change.after].iter().flatten().any(|standing| standing.rank <= options.top)
+        })
+        .collect::<Vec<_>>();
+    leaderboard.sort_by_key(|change| {
+        let rank = |standing: Option<Standing>| standing.map_or(usize::MAX, |standing| standing.rank);
+        (rank(change.after), rank(change.before))
+    });
+
+    Ok(InventoryDiff { elves_before, elves_after, unchanged, changes, top: options.top, leaderboard, note })
+}
+
+// Each elf's rank, indexed by its 0-based position.
+fn ranks(leaderboard: Leaderboard) -> Vec<usize> {
+    let mut ranks = vec![0; leaderboard.rankings.len()];
+    for ranking in leaderboard.rankings {
+        ranks[ranking.elf_id - 1] = ranking.rank;
+    }
+    ranks
+}
+
+fn by_position(before: usize, after: usize) -> Pairs {
+    (0..before.max(after))
+        .map(|index| ((index < before).then_some(index), (index < after).then_some(index)))
+        .collect()
+}
+
+// Shares of items in common, 1.0 for identical elves.
+fn similarity(a: &[u32], b: &[u32]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0
+    }
+    2.0 * multiset::common(a, b).len() as f64 / (a.len() + b.len()) as f64
+}
+
+// Pairs elves in order so the summed similarity of the pairs is as large as it can be, in the manner
+// of a sequence alignment. Identical runs at either end are paired up front, so the table only
+// covers the part of the inventory that actually changed; when that part still needs more than
+// `TABLE_LIMIT` cells, its size is returned instead.
+fn by_content(old: &[Elf], new: &[Elf]) -> Result<Pairs, (usize, usize)> {
+    let old = old.iter().map(|elf| multiset::sorted(&elf.items)).collect::<Vec<_>>();
+    let new = new.iter().map(|elf| multiset::sorted(&elf.items)).collect::<Vec<_>>();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (n, m) = (old.len() - prefix - suffix, new.len() - prefix - suffix);
+    if (n as u128 + 1) * (m as u128 + 1) > TABLE_LIMIT {
+        return Err((n, m))
+    }
+
+    // best[i][j]: the most similarity the first i changed old elves and first j changed new elves can share
+    let mut best = vec![vec![0.0f64; m + 1]; n + 1];
+    for i in 1..=n {
+        for j in 1..=m {
+            let mut score = best[i - 1][j].max(best[i][j - 1]);
+            let alike = similarity(&old[prefix + i - 1], &new[prefix + j - 1]);
+            if alike >= MIN_SIMILARITY {
+                score = score.max(best[i - 1][j - 1] + alike);
+            }
+            best[i][j] = score;
+        }
+    }
+
+    let mut middle = Vec::with_capacity(n + m);
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 {
+            let alike = similarity(&old[prefix + i - 1], &new[prefix + j - 1]);
+            if alike >= MIN_SIMILARITY && best[i][j] == best[i - 1][j - 1] + alike {
+                middle.push((Some(prefix + i - 1), Some(prefix + j - 1)));
+                i -= 1;
+                j -= 1;
+                continue
+            }
+        }
+        if i > 0 && (j == 0 || best[i][j] == best[i - 1][j]) {
+            middle.push((Some(prefix + i - 1), None));
+            i -= 1;
+        } else {
+            middle.push((None, Some(prefix + j - 1)));
+            j -= 1;
+        }
+    }
+    middle.reverse();
+
+    let head = (0..prefix).map(|index| (Some(index), Some(index)));
+    let tail = (0..suffix).map(|offset| (Some(prefix + n + offset), Some(prefix + m + offset)));
+    Ok(head.chain(middle).chain(tail).collect())
+}
+
+fn items(items: &[u32]) -> String {
+    if items.is_empty() {
+        return "no items".to_string()
+    }
+    items.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn describe(standing: Option<Standing>) -> String {
+    match standing {
+        Some(standing) => format!("#{} elf {}", standing.rank, standing.elf),
+        None => "-".to_string(),
+    }
+}
+
+impl fmt::Display for InventoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(note) = &self.note {
+            writeln!(f, "note: {}", note)?;
+        }
+        writeln!(f, "elves: {} -> {}, {} unchanged", self.elves_before, self.elves_after, self.unchanged)?;
+        for change in &self.changes {
+            match change {
+                ElfChange::Added { after, items: added } => writeln!(f, "+ elf {}: {}", after, items(added))?,
+                ElfChange::Removed { before, items: removed } => writeln!(f, "- elf {}: {}", before, items(removed))?,
+                ElfChange::Changed { before, after, lost, gained } => {
+                    let lost = lost.iter().map(|cals| format!(" -{}", cals));
+                    let gained = gained.iter().map(|cals| format!(" +{}", cals));
+                    writeln!(f, "~ elf {} -> {}:{}", before, after, lost.chain(gained).collect::<String>())?
+                }
+            }
+        }
+        writeln!(f, "top {}:", self.top)?;
+        for change in &self.leaderboard {
+            writeln!(f, "  {} -> {}", describe(change.before), describe(change.after))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn before() -> Vec<Vec<u32>> {
+        vec![vec![1, 2], vec![3], vec![4, 5], vec![6]]
+    }
+
+    fn after() -> Vec<Vec<u32>> {
+        vec![vec![2, 1], vec![9], vec![3], vec![4, 5, 7]]
+    }
+
+    #[test]
+    fn content_alignment_finds_insertions_and_removals() {
+        // given
+        let options = DiffOptions { alignment: Alignment::Content, top: 2 };
+
+        // when
+        let actual = diff(&before(), &after(), &options).unwrap();
+
+        // then
+        let expected = vec![
+            ElfChange::Added { after: 2, items: vec![9] },
+            ElfChange::Changed { before: 3, after: 4, lost: vec![], gained: vec![7] },
+            ElfChange::Removed { before: 4, items: vec![6] },
+        ];
+        assert_eq!(expected, actual.changes);
+        assert_eq!(2, actual.unchanged);
+    }
+
+    #[test]
+    fn position_alignment_pairs_by_index() {
+        let options = DiffOptions { alignment: Alignment::Position, top: 2 };
+
+        let actual = diff(&before(), &after(), &options).unwrap();
+
+        assert_eq!(1, actual.unchanged);
+        assert!(actual.changes.iter().all(|change| matches!(change, ElfChange::Changed { .. })));
+    }
+
+    #[test]
+    fn large_changes_fall_back_to_position_alignment() {
+        // given: every elf changes, so the whole inventory would need aligning
+        let before = (0..5000).map(|cals| vec![cals]).collect::<Vec<_>>();
+        let after = (0..5000).map(|cals| vec![cals + 5000]).collect::<Vec<_>>();
+
+        // when
+        let actual = diff(&before, &after, &DiffOptions::default()).unwrap();
+
+        // then
+        assert!(actual.note.is_some());
+        assert_eq!(5000, actual.changes.len());
+        assert!(actual.changes.iter().all(|change| matches!(change, ElfChange::Changed { before, after, .. } if before == after)));
+    }
+
+    #[test]
+    fn can_follow_the_leaderboard() {
+        // given
+        let options = DiffOptions { alignment: Alignment::Content, top: 2 };
+
+        // when
+        let actual = diff(&before(), &after(), &options).unwrap();
+
+        // then
+        let at = |elf, rank| Some(Standing { elf, rank });
+        let expected = vec![
+            RankChange { before: at(3, 1), after: at(4, 1) },
+            RankChange { before: None, after: at(2, 2) },
+            RankChange { before: at(4, 2), after: None },
+        ];
+        assert_eq!(expected, actual.leaderboard);
+    }
+}
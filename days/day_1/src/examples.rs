@@ -0,0 +1,17 @@
+use aoc_common::{examples, Input, Part};
+use crate::{
+    file_reader::FileReader,
+    inventory_counter::InventoryCounter,
+};
+
+#[test]
+fn examples_match_expected_answers() {
+    examples::check_examples(1, |path, part| {
+        let inventory_counter = InventoryCounter::new(FileReader::new(Input::File(path.to_path_buf())));
+        let answer = match part {
+            Part::One => inventory_counter.highest_elf_calories_held()?,
+            Part::Two => inventory_counter.top_three_elf_calories_held()?,
+        };
+        Ok(answer.to_string())
+    });
+}
@@ -1,48 +1,214 @@
-use std::fmt::Debug;
-use std::fs::File;
 use std::io;
-use std::io::BufRead;
-use std::path::Path;
-use crate::inventory_counter::Reader;
-use crate::error::Result;
+use aoc_common::{Error, Input, Result};
+use crate::inventory_counter::{self, Elf, ElfTotals, Reader};
+
+/// How forgiving the reader is about lines that are not calorie counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Any unreadable or non-numeric line ends the current elf, as it always has.
+    #[default]
+    Lenient,
+    /// Only a single blank line ends an elf; anything else that is not a number is an error.
+    Strict,
+}
 
 pub struct FileReader {
-    file_path: String
+    input: Input,
+    mode: ParseMode,
 }
 
 impl FileReader {
-    pub fn new(file_path: String) -> Self {
-        FileReader { file_path }
+    pub fn new(input: Input) -> Self {
+        FileReader { input, mode: ParseMode::Lenient }
+    }
+
+    pub fn strict(input: Input) -> Self {
+        FileReader { input, mode: ParseMode::Strict }
     }
 }
 
 impl Reader for FileReader {
     fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
-        let (_, elves) = read_lines(&self.file_path)
-            .map_err(error_to_string)?
-            .map(|maybe_e| maybe_e.map_err(error_to_string))
-            .map(|ip| ip.ok().and_then(|val| val.parse::<u32>().ok()))
-            .fold((Vec::new(), Vec::new()), |(mut elf, mut elves), maybe_cals| {
-                if let Some(cals) = maybe_cals {
-                    elf.push(cals);
-                } else {
-                    elves.push(elf);
-                    elf = Vec::new();
-                }
-                (elf, elves)
-            });
-        Ok(elves)
+        inventory_from_lines(self.input.lines()?, self.mode)
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        elves_from_lines(self.input.lines()?, self.mode)
+    }
+
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        let totals = Elves::with_mode(self.input.lines()?, self.mode).map(|elf| inventory_counter::elf_total(&elf?.items));
+        Ok(Box::new(totals))
     }
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+pub(crate) fn inventory_from_lines<I>(lines: I, mode: ParseMode) -> Result<Vec<Vec<u32>>>
     where
-        P: AsRef<Path>,
+        I: Iterator<Item = io::Result<String>>,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+    Ok(elves_from_lines(lines, mode)?.into_iter().map(|elf| elf.items).collect())
+}
+
+fn elves_from_lines<I>(lines: I, mode: ParseMode) -> Result<Vec<Elf>>
+    where
+        I: Iterator<Item = io::Result<String>>,
+{
+    let elves = Elves::with_mode(lines, mode).collect::<Result<Vec<_>>>()?;
+    if elves.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    Ok(elves)
+}
+
+/// Splits inventory lines into elves lazily, holding only the elf being read.
+pub struct Elves<I> {
+    lines: I,
+    mode: ParseMode,
+    line_number: usize,
+    next_id: usize,
+    failed: bool,
+}
+
+impl<I> Elves<I> {
+    pub fn new(lines: I) -> Self {
+        Elves::with_mode(lines, ParseMode::Lenient)
+    }
+
+    pub fn with_mode(lines: I, mode: ParseMode) -> Self {
+        Elves { lines, mode, line_number: 0, next_id: 1, failed: false }
+    }
+}
+
+impl<I> Iterator for Elves<I>
+    where
+        I: Iterator<Item = io::Result<String>>,
+{
+    type Item = Result<Elf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None
+        }
+        let first_line = self.line_number + 1;
+        let mut elf = Vec::new();
+        for line in self.lines.by_ref() {
+            self.line_number += 1;
+            if self.mode == ParseMode::Lenient {
+                match line.ok().and_then(|val| val.parse::<u32>().ok()) {
+                    Some(cals) => elf.push(cals),
+                    None => return Some(Ok(self.finish(first_line, elf))),
+                }
+                continue
+            }
+            let checked = match line {
+                Ok(line) if line.is_empty() && elf.is_empty() => Err(Error::parse(
+                    self.line_number,
+                    1,
+                    "blank line would start an empty elf",
+                )),
+                Ok(line) if line.is_empty() => return Some(Ok(self.finish(first_line, elf))),
+                Ok(line) => line.parse::<u32>().map_err(|_| Error::parse(
+                    self.line_number,
+                    1,
+                    format!("not a calorie count: {:?}", line),
+                )),
+                Err(e) => Err(e.into()),
+            };
+            match checked {
+                Ok(cals) => elf.push(cals),
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(e))
+                }
+            }
+        }
+        if elf.is_empty() {
+            None
+        } else {
+            self.line_number += 1;
+            Some(Ok(self.finish(first_line, elf)))
+        }
+    }
 }
 
-fn error_to_string<E: Debug>(e: E) -> String {
-    format!("Error: {:?}", e)
+impl<I> Elves<I> {
+    // Called once the line after the elf's last item has been counted.
+    fn finish(&mut self, first_line: usize, items: Vec<u32>) -> Elf {
+        let id = self.next_id;
+        self.next_id += 1;
+        Elf { id, lines: first_line..self.line_number, items }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(raw: &str) -> impl Iterator<Item = io::Result<String>> + '_ {
+        raw.lines().map(|line| Ok(line.to_string()))
+    }
+
+    fn items<I: Iterator<Item = Result<Elf>>>(elves: I) -> Result<Vec<Vec<u32>>> {
+        elves.map(|elf| Ok(elf?.items)).collect()
+    }
+
+    #[test]
+    fn elves_keep_the_trailing_elf() {
+        let expected = vec![vec![1, 2], vec![3]];
+
+        let actual = items(Elves::new(lines("1\n2\n\n3"))).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn lenient_treats_bad_lines_as_separators() {
+        let expected = vec![vec![1], vec![], vec![2]];
+
+        let actual = items(Elves::new(lines("1\n\nten\n2\n"))).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn strict_reports_non_numeric_lines() {
+        let actual = items(Elves::with_mode(lines("1\n2\n\n3x\n"), ParseMode::Strict)).unwrap_err();
+
+        match actual {
+            Error::Parse { line, message, .. } => {
+                assert_eq!(4, line);
+                assert!(message.contains("3x"));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn strict_flags_repeated_blank_lines() {
+        let actual = items(Elves::with_mode(lines("1\n\n\n2\n"), ParseMode::Strict)).unwrap_err();
+
+        assert!(matches!(actual, Error::Parse { line: 3, .. }));
+    }
+
+    #[test]
+    fn strict_keeps_the_trailing_elf() {
+        let expected = vec![vec![1, 2], vec![3]];
+
+        let actual = items(Elves::with_mode(lines("1\n2\n\n3"), ParseMode::Strict)).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn elves_know_where_they_came_from() {
+        let expected = vec![
+            Elf { id: 1, lines: 1..3, items: vec![1, 2] },
+            Elf { id: 2, lines: 4..4, items: vec![] },
+            Elf { id: 3, lines: 5..7, items: vec![3, 4] },
+        ];
+
+        let actual = Elves::new(lines("1\n2\n\n\n3\n4")).collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(expected, actual);
+    }
 }
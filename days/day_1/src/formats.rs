@@ -0,0 +1,124 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+use aoc_common::{Input, Result};
+use crate::{
+    csv_reader::CsvReader,
+    file_reader::FileReader,
+    inventory_counter::Reader,
+    json_reader::JsonReader,
+};
+
+/// The ways an inventory can be written down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InventoryFormat {
+    /// The puzzle's own layout: one calorie count per line, blank lines between elves.
+    Text,
+    /// An array of arrays, one inner array per elf.
+    Json,
+    /// `elf_id,calories` rows, one per item.
+    Csv,
+}
+
+impl InventoryFormat {
+    /// Guesses from a file's extension, then from the first non-blank line.
+    pub fn detect(input: &Input) -> Result<Self> {
+        if let Input::File(path) = input {
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some("json") => return Ok(InventoryFormat::Json),
+                Some("csv") => return Ok(InventoryFormat::Csv),
+                Some("txt") => return Ok(InventoryFormat::Text),
+                _ => {}
+            }
+        }
+        for line in input.lines()? {
+            let line = line?;
+            let line = line.trim();
+            if line.starts_with('[') {
+                return Ok(InventoryFormat::Json)
+            } else if line.contains(',') {
+                return Ok(InventoryFormat::Csv)
+            } else if !line.is_empty() {
+                break
+            }
+        }
+        Ok(InventoryFormat::Text)
+    }
+
+    pub fn reader(self, input: Input) -> Box<dyn Reader> {
+        match self {
+            InventoryFormat::Text => Box::new(FileReader::new(input)),
+            InventoryFormat::Json => Box::new(JsonReader::new(input)),
+            InventoryFormat::Csv => Box::new(CsvReader::new(input)),
+        }
+    }
+}
+
+/// A reader for `input` in `format`, or in whichever format it appears to be in when none is given.
+pub fn open_inventory(input: Input, format: Option<InventoryFormat>) -> Result<Box<dyn Reader>> {
+    let format = match format {
+        Some(format) => format,
+        None => InventoryFormat::detect(&input)?,
+    };
+    Ok(format.reader(input))
+}
+
+impl FromStr for InventoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(InventoryFormat::Text),
+            "json" => Ok(InventoryFormat::Json),
+            "csv" => Ok(InventoryFormat::Csv),
+            other => Err(format!("unknown inventory format: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for InventoryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryFormat::Text => write!(f, "text"),
+            InventoryFormat::Json => write!(f, "json"),
+            InventoryFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use super::*;
+    use crate::inventory_counter::InventoryCounter;
+
+    #[test]
+    fn can_detect_formats() {
+        let detect = |text: &'static str| InventoryFormat::detect(&Input::Stdin(text.to_string())).unwrap();
+
+        assert_eq!(InventoryFormat::Json, detect("\n [[1, 2], [3]]"));
+        assert_eq!(InventoryFormat::Csv, detect("elf_id,calories\n1,5\n"));
+        assert_eq!(InventoryFormat::Text, detect("1\n2\n\n3\n"));
+        assert_eq!(InventoryFormat::Csv, InventoryFormat::detect(&Input::File(PathBuf::from("missing.csv"))).unwrap());
+    }
+
+    #[test]
+    fn every_format_feeds_the_same_counter() {
+        // given
+        let inputs = [
+            (InventoryFormat::Text, "1000\n2000\n\n4000\n\n5000\n6000\n"),
+            (InventoryFormat::Json, "[[1000, 2000], [4000], [5000, 6000]]"),
+            (InventoryFormat::Csv, "a,1000\na,2000\nb,4000\nc,5000\nc,6000\n"),
+        ];
+
+        for (format, text) in inputs {
+            // when
+            let reader = open_inventory(Input::Stdin(text.to_string()), None).unwrap();
+            let actual = InventoryCounter::new(reader).calorie_summary().unwrap();
+
+            // then
+            assert_eq!((11000, 18000), (actual.highest, actual.top_three), "{}", format);
+        }
+    }
+}
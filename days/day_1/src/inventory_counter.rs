@@ -1,7 +1,113 @@
-use crate::error::Result;
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    ops::Range,
+};
+use aoc_common::{Error, Result};
+use crate::subset::{self, Subset, Target};
+
+/// Most heap slots `top_k` reserves up front; the elf count is not known until the totals run out.
+const TOP_K_RESERVE: usize = 1024;
+
+/// Elf totals produced one at a time, so an inventory never has to be held in memory.
+pub type ElfTotals<'a> = Box<dyn Iterator<Item = Result<u64>> + 'a>;
+
+/// Sums an elf's items in 64 bits, so no realistic inventory can overflow; one that does is an error, never a wrap.
+pub fn elf_total(items: &[u32]) -> Result<u64> {
+    items
+        .iter()
+        .try_fold(0u64, |total, cals| total.checked_add(*cals as u64))
+        .ok_or_else(|| Error::overflow("an elf's calories do not fit in 64 bits"))
+}
+
+/// One elf's items, identified by its 1-based position in the input and the lines it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Elf {
+    pub id: usize,
+    /// 1-based input line numbers, end exclusive; empty for an elf with no items.
+    pub lines: Range<usize>,
+    pub items: Vec<u32>,
+}
 
 pub trait Reader {
     fn read_inventory(&self) -> Result<Vec<Vec<u32>>>;
+
+    /// Every elf with its identity. Readers that know where each elf came from should override this;
+    /// by default line ranges assume the puzzle layout of one item per line and one blank line between elves.
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        let mut next_line = 1;
+        let elves = self
+            .read_inventory()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, items)| {
+                let lines = next_line..next_line + items.len();
+                next_line = lines.end + 1;
+                Elf { id: index + 1, lines, items }
+            })
+            .collect();
+        Ok(elves)
+    }
+
+    /// Each elf's total calories in input order. Readers that can stream should override this.
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        let totals = self
+            .read_inventory()?
+            .into_iter()
+            .map(|elf| elf_total(&elf));
+        Ok(Box::new(totals))
+    }
+}
+
+impl Reader for Vec<Vec<u32>> {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        Ok(self.clone())
+    }
+
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        Ok(Box::new(self.iter().map(|elf| elf_total(elf))))
+    }
+}
+
+impl<R: Reader + ?Sized> Reader for &R {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        (**self).read_inventory()
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        (**self).read_elves()
+    }
+
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        (**self).elf_totals()
+    }
+}
+
+impl<R: Reader + ?Sized> Reader for Box<R> {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        (**self).read_inventory()
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        (**self).read_elves()
+    }
+
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        (**self).elf_totals()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CalorieSummary {
+    pub highest: u64,
+    pub top_three: u64,
+}
+
+/// One elf's calories, with its 0-based position in the inventory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElfTotal {
+    pub index: usize,
+    pub total: u64,
 }
 
 pub struct InventoryCounter<R: Reader> {
@@ -15,44 +121,60 @@ impl<R: Reader> InventoryCounter<R> {
         }
     }
 
-    pub fn highest_elf_calories_held(&self) -> Result<u32> {
-        let inventory = self.reader.read_inventory()?;
-        let mut highest = 0;
+    pub fn highest_elf_calories_held(&self) -> Result<u64> {
+        Ok(self.calorie_summary()?.highest)
+    }
 
-        for elf in inventory {
-            let elf_cals = elf.iter().fold(0, |acc, cals| acc + cals );
-            if elf_cals > highest {
-                highest = elf_cals
+    pub fn top_three_elf_calories_held(&self) -> Result<u64> {
+        Ok(self.calorie_summary()?.top_three)
+    }
+
+    /// Answers both queries in a single pass over the reader's elf totals.
+    pub fn calorie_summary(&self) -> Result<CalorieSummary> {
+        let top_three = self.top_k(3)?;
+        let combined = top_three
+            .iter()
+            .try_fold(0u64, |sum, elf| sum.checked_add(elf.total))
+            .ok_or_else(|| Error::overflow("the top three elves' calories do not fit in 64 bits"))?;
+        Ok(CalorieSummary {
+            highest: top_three[0].total,
+            top_three: combined,
+        })
+    }
+
+    /// The `k` elves holding the most calories, largest first; ties go to the earlier elf.
+    ///
+    /// Keeps a min-heap of the best `k` seen so far, so this is O(n log k) in time and O(k) in memory.
+    pub fn top_k(&self, k: usize) -> Result<Vec<ElfTotal>> {
+        let mut seen_elf = false;
+        let mut heap = BinaryHeap::with_capacity(k.saturating_add(1).min(TOP_K_RESERVE));
+
+        for (index, total) in self.reader.elf_totals()?.enumerate() {
+            let total = total?;
+            seen_elf = true;
+            heap.push(Reverse((total, Reverse(index))));
+            if heap.len() > k {
+                heap.pop();
             }
         }
-        Ok(highest)
-    }
-
-    pub fn top_three_elf_calories_held(&self) -> Result<u32> {
-        let inventory = self.reader.read_inventory()?;
-        let mut one = 0;
-        let mut two = 0;
-        let mut three = 0;
-
-        for elf in inventory {
-            let elf_cals = elf.iter().fold(0, |acc, cals| acc + cals );
-            if elf_cals > three {
-                if elf_cals > two {
-                    if elf_cals > one{
-                        three = two;
-                        two = one;
-                        one = elf_cals;
-                    } else {
-                        three = two;
-                        two = elf_cals;
-                    }
-                } else {
-                    three = elf_cals
-                }
-            } else {
-            }
+        if !seen_elf {
+            return Err(Error::EmptyInput)
         }
-        Ok(one + two + three)
+        let top = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| ElfTotal { index, total })
+            .collect();
+        Ok(top)
+    }
+
+    /// The fewest elves holding `target` calories between them.
+    pub fn fewest_elves_holding(&self, target: Target) -> Result<Subset> {
+        let totals = self.reader.elf_totals()?.collect::<Result<Vec<_>>>()?;
+        if totals.is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        subset::fewest_elves(&totals, target)
     }
 }
 
@@ -90,7 +212,7 @@ mod tests {
         let actual = inventory_counter.highest_elf_calories_held().unwrap();
 
         // then
-        let expected: u32 = 3 + 4 + 5;
+        let expected: u64 = 3 + 4 + 5;
         assert_eq!(expected, actual)
     }
 
@@ -106,7 +228,119 @@ mod tests {
         let actual = inventory_counter.top_three_elf_calories_held().unwrap();
 
         // then
-        let expected: u32 = (3 + 4 + 5)+ (2 + 3 + 2) + (1 + 2 + 3);
+        let expected: u64 = (3 + 4 + 5)+ (2 + 3 + 2) + (1 + 2 + 3);
+        assert_eq!(expected, actual)
+    }
+
+    struct StreamOnlyReader {
+        totals: Vec<u64>
+    }
+
+    impl Reader for StreamOnlyReader {
+        fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+            panic!("the inventory should be streamed, not materialized")
+        }
+
+        fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+            Ok(Box::new(self.totals.iter().map(|total| Ok(*total))))
+        }
+    }
+
+    #[test]
+    fn default_elves_follow_the_puzzle_layout() {
+        // given
+        let reader = FakeReader::new(vec![vec![1, 2], vec![], vec![3]]);
+
+        // when
+        let actual = reader.read_elves().unwrap();
+
+        // then
+        let expected = vec![
+            Elf { id: 1, lines: 1..3, items: vec![1, 2] },
+            Elf { id: 2, lines: 4..4, items: vec![] },
+            Elf { id: 3, lines: 5..6, items: vec![3] },
+        ];
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn can_summarise_from_a_stream() {
+        // given
+        let reader = StreamOnlyReader { totals: vec![6, 12, 1, 7] };
+        let inventory_counter = InventoryCounter::new(reader);
+
+        // when
+        let actual = inventory_counter.calorie_summary().unwrap();
+
+        // then
+        let expected = CalorieSummary { highest: 12, top_three: 12 + 7 + 6 };
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn can_get_top_k_with_indices() {
+        // given
+        let reader = StreamOnlyReader { totals: vec![6, 12, 1, 7, 12, 3] };
+        let inventory_counter = InventoryCounter::new(reader);
+
+        // when
+        let actual = inventory_counter.top_k(4).unwrap();
+
+        // then
+        let expected = vec![
+            ElfTotal { index: 1, total: 12 },
+            ElfTotal { index: 4, total: 12 },
+            ElfTotal { index: 3, total: 7 },
+            ElfTotal { index: 0, total: 6 },
+        ];
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn top_k_larger_than_inventory_returns_everyone() {
+        let inventory_counter = InventoryCounter::new(StreamOnlyReader { totals: vec![2, 5] });
+
+        let actual = inventory_counter.top_k(10).unwrap();
+        let unbounded = inventory_counter.top_k(usize::MAX).unwrap();
+
+        let expected = vec![ElfTotal { index: 1, total: 5 }, ElfTotal { index: 0, total: 2 }];
+        assert_eq!(expected, actual);
+        assert_eq!(expected, unbounded)
+    }
+
+    #[test]
+    fn totals_past_u32_do_not_wrap() {
+        let inventory_counter = InventoryCounter::new(vec![vec![u32::MAX, u32::MAX], vec![1]]);
+
+        let actual = inventory_counter.highest_elf_calories_held().unwrap();
+
+        assert_eq!(2 * u32::MAX as u64, actual)
+    }
+
+    #[test]
+    fn overflowing_summary_is_an_error() {
+        let inventory_counter = InventoryCounter::new(StreamOnlyReader { totals: vec![u64::MAX, 1] });
+
+        let actual = inventory_counter.calorie_summary().unwrap_err();
+
+        assert!(matches!(actual, Error::Overflow(_)));
+    }
+
+    #[test]
+    fn can_find_elves_holding_a_target() {
+        let inventory_counter = InventoryCounter::new(StreamOnlyReader { totals: vec![6, 12, 1, 7] });
+
+        let actual = inventory_counter.fewest_elves_holding(Target::Exactly(14)).unwrap();
+
+        assert_eq!(Subset { indices: vec![0, 2, 3], total: 14 }, actual)
+    }
+
+    #[test]
+    fn empty_stream_is_an_error() {
+        let inventory_counter = InventoryCounter::new(StreamOnlyReader { totals: vec![] });
+
+        let actual = inventory_counter.calorie_summary().unwrap_err();
+
+        assert!(matches!(actual, Error::EmptyInput));
+    }
 }
@@ -0,0 +1,86 @@
+use aoc_common::{json::Cursor, Error, Input, Result};
+use crate::inventory_counter::{Elf, Reader};
+
+/// Reads an inventory exported as a JSON array of arrays, one inner array of calories per elf.
+pub struct JsonReader {
+    input: Input,
+}
+
+impl JsonReader {
+    pub fn new(input: Input) -> Self {
+        JsonReader { input }
+    }
+}
+
+impl Reader for JsonReader {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        Ok(self.read_elves()?.into_iter().map(|elf| elf.items).collect())
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        let elves = elves_from_json(&self.input.read_to_string()?)?;
+        if elves.is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        Ok(elves)
+    }
+}
+
+/// Each elf's `lines` cover the lines its inner array spans.
+pub(crate) fn elves_from_json(raw: &str) -> Result<Vec<Elf>> {
+    let mut cursor = Cursor::new(raw);
+    let mut elves = Vec::new();
+    cursor.array(|cursor| {
+        let first_line = cursor.line();
+        let mut items = Vec::new();
+        cursor.array(|cursor| {
+            items.push(cursor.number("a calorie count")?);
+            Ok(())
+        })?;
+        elves.push(Elf { id: elves.len() + 1, lines: first_line..cursor.line() + 1, items });
+        Ok(())
+    })?;
+    cursor.end("inventory")?;
+    Ok(elves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_read_nested_arrays() {
+        // given
+        let raw = "[\n  [1000, 2000],\n  [],\n  [\n    3000\n  ]\n]\n";
+
+        // when
+        let actual = elves_from_json(raw).unwrap();
+
+        // then
+        let expected = vec![
+            Elf { id: 1, lines: 2..3, items: vec![1000, 2000] },
+            Elf { id: 2, lines: 3..4, items: vec![] },
+            Elf { id: 3, lines: 4..7, items: vec![3000] },
+        ];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn reports_where_a_bad_value_is() {
+        let actual = elves_from_json("[[1, 2],\n [3, -4]]").unwrap_err();
+
+        match actual {
+            Error::Parse { line, column, message } => {
+                assert_eq!((2, 6), (line, column));
+                assert!(message.contains("-4"));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_trailing_content() {
+        assert!(elves_from_json("[[1]] [[2]]").is_err());
+        assert!(elves_from_json("[[1],]").is_err());
+    }
+}
@@ -0,0 +1,18 @@
+pub mod inventory_counter;
+pub mod csv_reader;
+pub mod diff;
+pub mod file_reader;
+pub mod formats;
+pub mod json_reader;
+pub mod mapped_reader;
+mod multiset;
+pub mod ranking;
+pub mod rebalance;
+pub mod solution;
+pub mod statistics;
+pub mod subset;
+
+#[cfg(test)]
+mod examples;
+
+pub const DATA: &str = include_str!("../../../data/day_1.txt");
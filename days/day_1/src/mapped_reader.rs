@@ -0,0 +1,227 @@
+use std::{
+    fs::File,
+    num::NonZeroUsize,
+    path::PathBuf,
+    thread,
+};
+use memmap2::Mmap;
+use aoc_common::{Error, Result};
+use crate::inventory_counter::{Elf, ElfTotals, Reader};
+
+/// Reads a text inventory by memory-mapping the file and parsing pieces of it on several threads.
+///
+/// It follows the same lenient rules as [`FileReader`](crate::file_reader::FileReader), so anything that is not
+/// a calorie count ends an elf, and the results are exactly the same. The pieces are cut just after such lines.
+/// The file must not change while it is being read.
+pub struct MappedReader {
+    path: PathBuf,
+    threads: usize,
+}
+
+impl MappedReader {
+    /// Uses as many threads as the machine offers.
+    pub fn new(path: PathBuf) -> Self {
+        let threads = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        MappedReader { path, threads }
+    }
+
+    pub fn with_threads(path: PathBuf, threads: usize) -> Self {
+        MappedReader { path, threads: threads.max(1) }
+    }
+
+    // Parses every chunk on its own thread, in order.
+    fn parse<T, F>(&self, parse_chunk: F) -> Result<Vec<T>>
+        where
+            T: Send,
+            F: Fn(&[u8], usize) -> Result<T> + Sync,
+    {
+        let file = File::open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            return Err(Error::EmptyInput)
+        }
+        // SAFETY: the map is only read, and the caller promises the file is not modified meanwhile.
+        let map = unsafe { Mmap::map(&file)? };
+        let chunks = chunks(&map, self.threads);
+
+        thread::scope(|scope| {
+            let parse_chunk = &parse_chunk;
+            let handles = chunks
+                .into_iter()
+                .map(|(chunk, first_line)| scope.spawn(move || parse_chunk(chunk, first_line)))
+                .collect::<Vec<_>>();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("a parsing thread panicked"))
+                .collect()
+        })
+    }
+}
+
+impl Reader for MappedReader {
+    fn read_inventory(&self) -> Result<Vec<Vec<u32>>> {
+        Ok(self.read_elves()?.into_iter().map(|elf| elf.items).collect())
+    }
+
+    fn read_elves(&self) -> Result<Vec<Elf>> {
+        let mut elves = self.parse(elves_in)?.into_iter().flatten().collect::<Vec<_>>();
+        if elves.is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        for (index, elf) in elves.iter_mut().enumerate() {
+            elf.id = index + 1;
+        }
+        Ok(elves)
+    }
+
+    fn elf_totals(&self) -> Result<ElfTotals<'_>> {
+        let totals = self.parse(|chunk, _| totals_in(chunk))?.into_iter().flatten().map(Ok);
+        Ok(Box::new(totals))
+    }
+}
+
+// Cuts `data` into about `count` pieces, each ending just after a line that ends an elf, and
+// pairs each piece with the 1-based number of its first line.
+fn chunks(data: &[u8], count: usize) -> Vec<(&[u8], usize)> {
+    let target = data.len().div_ceil(count).max(1);
+    let mut chunks = Vec::with_capacity(count);
+    let mut start = 0;
+    let mut first_line = 1;
+    while start < data.len() {
+        let mut end = (start + target).min(data.len());
+        // finish the line `end` falls in, then keep going until a separator has been passed
+        while end < data.len() && data[end - 1] != b'\n' {
+            end += 1;
+        }
+        while end < data.len() {
+            let line_end = data[end..].iter().position(|b| *b == b'\n').map_or(data.len(), |i| end + i + 1);
+            let separator = calories(&data[end..line_end]).is_none();
+            end = line_end;
+            if separator {
+                break
+            }
+        }
+        let chunk = &data[start..end];
+        chunks.push((chunk, first_line));
+        first_line += chunk.iter().filter(|b| **b == b'\n').count();
+        start = end;
+    }
+    chunks
+}
+
+// The line's calorie count, or `None` when it ends an elf. Line endings are dropped the way `BufRead::lines` does.
+fn calories(line: &[u8]) -> Option<u32> {
+    let line = match line.strip_suffix(b"\n") {
+        Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+        None => line,
+    };
+    std::str::from_utf8(line).ok()?.parse().ok()
+}
+
+// Each line with its newline, if it has one.
+fn lines(chunk: &[u8]) -> impl Iterator<Item = &[u8]> {
+    chunk.split_inclusive(|b| *b == b'\n')
+}
+
+// Elves in one chunk, numbered later once every chunk is in.
+fn elves_in(chunk: &[u8], first_line: usize) -> Result<Vec<Elf>> {
+    let mut elves = Vec::new();
+    let mut items = Vec::new();
+    let mut start = first_line;
+    let mut line_number = first_line - 1;
+    for line in lines(chunk) {
+        line_number += 1;
+        match calories(line) {
+            Some(cals) => items.push(cals),
+            None => {
+                elves.push(Elf { id: 0, lines: start..line_number, items: std::mem::take(&mut items) });
+                start = line_number + 1;
+            }
+        }
+    }
+    if !items.is_empty() {
+        elves.push(Elf { id: 0, lines: start..line_number + 1, items });
+    }
+    Ok(elves)
+}
+
+fn totals_in(chunk: &[u8]) -> Result<Vec<u64>> {
+    let overflow = || Error::overflow("an elf's calories do not fit in 64 bits");
+    let mut totals = Vec::new();
+    let mut total = 0u64;
+    let mut has_items = false;
+    for line in lines(chunk) {
+        match calories(line) {
+            Some(cals) => {
+                total = total.checked_add(cals as u64).ok_or_else(overflow)?;
+                has_items = true;
+            }
+            None => {
+                totals.push(total);
+                total = 0;
+                has_items = false;
+            }
+        }
+    }
+    if has_items {
+        totals.push(total);
+    }
+    Ok(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use aoc_common::Input;
+    use super::*;
+    use crate::{
+        file_reader::FileReader,
+        inventory_counter::InventoryCounter,
+    };
+
+    fn scratch_file(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-mapped-{}-{}.txt", name, process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn assert_same_as_sequential(path: PathBuf) {
+        let sequential = FileReader::new(Input::File(path.clone()));
+        let expected_elves = sequential.read_elves().unwrap();
+        let expected_totals = sequential.elf_totals().unwrap().collect::<Result<Vec<_>>>().unwrap();
+        let expected_top = InventoryCounter::new(&sequential).top_k(5).unwrap();
+        let expected_summary = InventoryCounter::new(&sequential).calorie_summary().unwrap();
+
+        for threads in 1..=8 {
+            let mapped = MappedReader::with_threads(path.clone(), threads);
+
+            assert_eq!(expected_elves, mapped.read_elves().unwrap(), "{} threads", threads);
+            assert_eq!(expected_totals, mapped.elf_totals().unwrap().collect::<Result<Vec<_>>>().unwrap(), "{} threads", threads);
+            assert_eq!(expected_top, InventoryCounter::new(&mapped).top_k(5).unwrap(), "{} threads", threads);
+            assert_eq!(expected_summary, InventoryCounter::new(&mapped).calorie_summary().unwrap(), "{} threads", threads);
+        }
+    }
+
+    #[test]
+    fn matches_the_sequential_reader_on_the_puzzle_input() {
+        assert_same_as_sequential(PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../../data/day_1.txt")));
+    }
+
+    #[test]
+    fn matches_the_sequential_reader_on_messy_input() {
+        let contents = "\n\n1000\r\n2000\r\r\n\r\n3000\nten\n4000\n\n\n\n5000\n6000\n \n7000\n8000\n9000\n\n10000";
+        let path = scratch_file("messy", contents);
+
+        assert_same_as_sequential(path.clone());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn empty_file_is_empty_input() {
+        let path = scratch_file("empty", "");
+
+        let actual = MappedReader::with_threads(path.clone(), 4).read_inventory().unwrap_err();
+
+        assert!(matches!(actual, Error::EmptyInput));
+        fs::remove_file(path).unwrap();
+    }
+}
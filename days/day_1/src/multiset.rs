@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+pub(crate) fn sorted(items: &[u32]) -> Vec<u32> {
+    let mut items = items.to_vec();
+    items.sort_unstable();
+    items
+}
+
+/// Items in both; each side must be sorted ascending.
+pub(crate) fn common(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (mut i, mut j, mut both) = (0, 0, Vec::new());
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                both.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    both
+}
+
+/// `a` minus `b`; each side must be sorted ascending.
+pub(crate) fn difference(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut left = Vec::new();
+    let mut j = 0;
+    for item in a {
+        while j < b.len() && b[j] < *item {
+            j += 1;
+        }
+        if j < b.len() && b[j] == *item {
+            j += 1;
+        } else {
+            left.push(*item);
+        }
+    }
+    left
+}
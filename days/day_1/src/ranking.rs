@@ -0,0 +1,156 @@
+use std::{
+    fmt,
+    ops::Range,
+};
+use aoc_common::{Error, Result};
+use crate::inventory_counter::{self, Elf, Reader};
+
+/// One row of the leaderboard; elves with equal totals share a rank.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ranking {
+    pub rank: usize,
+    pub elf_id: usize,
+    pub lines: Range<usize>,
+    pub item_count: usize,
+    pub total: u64,
+}
+
+/// Every elf, most calories first; ties keep input order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub rankings: Vec<Ranking>,
+}
+
+impl Leaderboard {
+    pub fn from_reader<R: Reader>(reader: &R) -> Result<Self> {
+        Leaderboard::from_elves(reader.read_elves()?)
+    }
+
+    pub fn from_elves(elves: Vec<Elf>) -> Result<Self> {
+        let mut elves = elves
+            .into_iter()
+            .map(|elf| Ok((inventory_counter::elf_total(&elf.items)?, elf)))
+            .collect::<Result<Vec<_>>>()?;
+        if elves.is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        elves.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut rankings: Vec<Ranking> = Vec::with_capacity(elves.len());
+        for (position, (total, elf)) in elves.into_iter().enumerate() {
+            let rank = match rankings.last() {
+                Some(previous) if previous.total == total => previous.rank,
+                _ => position + 1,
+            };
+            rankings.push(Ranking {
+                rank,
+                elf_id: elf.id,
+                lines: elf.lines,
+                item_count: elf.items.len(),
+                total,
+            });
+        }
+        Ok(Leaderboard { rankings })
+    }
+
+    /// Keeps only the first `count` rows.
+    pub fn top(mut self, count: usize) -> Self {
+        self.rankings.truncate(count);
+        self
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("rank,elf,first_line,last_line,items,calories\n");
+        for ranking in &self.rankings {
+            let (first, last) = if ranking.lines.is_empty() {
+                (String::new(), String::new())
+            } else {
+                (ranking.lines.start.to_string(), (ranking.lines.end - 1).to_string())
+            };
+            csv.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                ranking.rank, ranking.elf_id, first, last, ranking.item_count, ranking.total
+            ));
+        }
+        csv
+    }
+}
+
+fn line_span(lines: &Range<usize>) -> String {
+    match lines.len() {
+        0 => "-".to_string(),
+        1 => lines.start.to_string(),
+        _ => format!("{}-{}", lines.start, lines.end - 1),
+    }
+}
+
+impl fmt::Display for Leaderboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header = ["rank", "elf", "lines", "items", "calories"];
+        let rows = self
+            .rankings
+            .iter()
+            .map(|ranking| [
+                ranking.rank.to_string(),
+                ranking.elf_id.to_string(),
+                line_span(&ranking.lines),
+                ranking.item_count.to_string(),
+                ranking.total.to_string(),
+            ])
+            .collect::<Vec<_>>();
+        let widths = (0..header.len())
+            .map(|column| rows.iter().map(|row| row[column].len()).chain([header[column].len()]).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+
+        let header = header.map(String::from);
+        for row in std::iter::once(&header).chain(&rows) {
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect::<Vec<_>>();
+            writeln!(f, "{}", cells.join("  "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_rank_elves() {
+        // given
+        let inventory = vec![vec![1, 2], vec![5], vec![], vec![3], vec![4, 1]];
+
+        // when
+        let actual = Leaderboard::from_reader(&inventory).unwrap();
+
+        // then
+        let ranks = actual
+            .rankings
+            .iter()
+            .map(|ranking| (ranking.rank, ranking.elf_id, ranking.item_count, ranking.total))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, 2, 1, 5), (1, 5, 2, 5), (3, 1, 2, 3), (3, 4, 1, 3), (5, 3, 0, 0)], ranks);
+        assert_eq!(1..3, actual.rankings[2].lines);
+    }
+
+    #[test]
+    fn can_render_table_and_csv() {
+        let leaderboard = Leaderboard::from_reader(&vec![vec![100, 200], vec![], vec![7]]).unwrap();
+
+        let table = leaderboard.to_string();
+        let csv = leaderboard.to_csv();
+
+        assert_eq!(
+            "rank  elf  lines  items  calories\n   1    1    1-2      2       300\n   2    3      5      1         7\n   3    2      -      0         0\n",
+            table
+        );
+        assert_eq!(
+            "rank,elf,first_line,last_line,items,calories\n1,1,1,2,2,300\n2,3,5,5,1,7\n3,2,,,0,0\n",
+            csv
+        );
+    }
+}
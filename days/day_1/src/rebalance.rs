@@ -0,0 +1,430 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap},
+    fmt,
+};
+use aoc_common::{Error, Result};
+use crate::{
+    inventory_counter::{self, Reader},
+    multiset,
+};
+
+/// Improving steps the local search takes before settling for what it has.
+const STEP_LIMIT: usize = 100_000;
+
+/// When the branch-and-bound search is worth running instead of the heuristic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RebalanceOptions {
+    /// The most items the exact search will take on; larger inventories use LPT plus local search.
+    pub exact_limit: usize,
+}
+
+impl Default for RebalanceOptions {
+    fn default() -> Self {
+        RebalanceOptions { exact_limit: 16 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Solver {
+    Exact,
+    Heuristic,
+}
+
+/// One snack handed from one elf to another; elves are 1-based.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ItemMove {
+    pub calories: u32,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The same snacks shared among the same elves so the heaviest load is as light as the solver could make it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rebalance {
+    pub solver: Solver,
+    /// Each elf's load before and after, in input order.
+    pub before: Vec<u64>,
+    pub after: Vec<u64>,
+    pub inventory: Vec<Vec<u32>>,
+    pub moves: Vec<ItemMove>,
+}
+
+impl Rebalance {
+    pub fn max_before(&self) -> u64 {
+        self.before.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn max_after(&self) -> u64 {
+        self.after.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Redistributes every item among the reader's elves to minimise the largest load,
+/// keeping as many items as it can with the elf already carrying them.
+pub fn rebalance<R: Reader>(reader: &R, options: &RebalanceOptions) -> Result<Rebalance> {
+    let original = reader.read_inventory()?;
+    if original.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    let before = original
+        .iter()
+        .map(|elf| inventory_counter::elf_total(elf))
+        .collect::<Result<Vec<_>>>()?;
+    let total = before
+        .iter()
+        .try_fold(0u64, |sum, load| sum.checked_add(*load))
+        .ok_or_else(|| Error::overflow("the inventory's calories do not fit in 64 bits"))?;
+
+    let mut items = original.iter().flatten().copied().collect::<Vec<_>>();
+    items.sort_unstable_by(|a, b| b.cmp(a));
+    let (solver, partition) = if items.len() <= options.exact_limit {
+        (Solver::Exact, exact(&items, original.len(), total))
+    } else {
+        // improving the current inventory moves few items, so it wins unless LPT does strictly better
+        let (in_place, _) = local_search(original.clone(), STEP_LIMIT);
+        let (repacked, _) = local_search(lpt(&items, original.len()), STEP_LIMIT);
+        let heaviest = |partition: &[Vec<u32>]| partition.iter().map(|elf| load(elf)).max();
+        if heaviest(&repacked) < heaviest(&in_place) {
+            (Solver::Heuristic, repacked)
+        } else {
+            (Solver::Heuristic, in_place)
+        }
+    };
+
+    let inventory = keep_items_in_place(&original, partition);
+    let moves = moves_between(&original, &inventory);
+    // every load is part of `total`, which fits
+    let after = inventory.iter().map(|elf| load(elf)).collect();
+    Ok(Rebalance { solver, before, after, inventory, moves })
+}
+
+fn load(items: &[u32]) -> u64 {
+    items.iter().map(|cals| *cals as u64).sum()
+}
+
+// Longest processing time first: each item, largest first, goes to the lightest bin.
+fn lpt(items: &[u32], bins: usize) -> Vec<Vec<u32>> {
+    let mut partition = vec![Vec::new(); bins];
+    let mut lightest = (0..bins).map(|bin| Reverse((0u64, bin))).collect::<BinaryHeap<_>>();
+    for item in items {
+        let Reverse((load, bin)) = lightest.pop().expect("there is at least one bin");
+        partition[bin].push(*item);
+        lightest.push(Reverse((load + *item as u64, bin)));
+    }
+    partition
+}
+
+// Moves or swaps an item out of the heaviest bin into the lightest bin it can go to without that bin
+// becoming as heavy, until no such step is left or `limit` steps were taken, and returns how many were. Each
+// step shrinks the loads sorted largest first, so this always stops. Loads are kept ordered and each bin's
+// items sorted, so a step costs the heaviest bin's items times a binary search, per bin tried.
+fn local_search(mut partition: Vec<Vec<u32>>, limit: usize) -> (Vec<Vec<u32>>, usize) {
+    partition.iter_mut().for_each(|bin| bin.sort_unstable());
+    let mut loads = partition.iter().map(|bin| load(bin)).collect::<Vec<_>>();
+    let mut order = loads.iter().enumerate().map(|(bin, load)| (*load, Reverse(bin))).collect::<BTreeSet<_>>();
+
+    for steps in 0..limit {
+        let (heaviest_load, Reverse(heaviest)) = *order.last().expect("there is at least one bin");
+        let step = order
+            .iter()
+            .take_while(|(_, Reverse(bin))| *bin != heaviest)
+            .find_map(|(other_load, Reverse(other))| {
+                improving_step(&partition[heaviest], &partition[*other], heaviest_load - other_load).map(|step| (*other, step))
+            });
+        let Some((other, (i, j))) = step else { return (partition, steps) };
+
+        order.remove(&(loads[heaviest], Reverse(heaviest)));
+        order.remove(&(loads[other], Reverse(other)));
+        let x = partition[heaviest].remove(i);
+        if let Some(j) = j {
+            let y = partition[other].remove(j);
+            insert_sorted(&mut partition[heaviest], y);
+            loads[heaviest] += y as u64;
+            loads[other] -= y as u64;
+        }
+        insert_sorted(&mut partition[other], x);
+        loads[heaviest] -= x as u64;
+        loads[other] += x as u64;
+        order.insert((loads[heaviest], Reverse(heaviest)));
+        order.insert((loads[other], Reverse(other)));
+    }
+    (partition, limit)
+}
+
+// An item of `heavy` to move, and maybe one of `light` to swap back, that shifts between 1 and `gap` - 1
+// calories from the heavy bin, so both bins end up lighter than the heavy one was. Both bins are sorted.
+fn improving_step(heavy: &[u32], light: &[u32], gap: u64) -> Option<(usize, Option<usize>)> {
+    // the largest item that fits outright
+    let fits = heavy.partition_point(|x| (*x as u64) < gap);
+    if fits > 0 && heavy[fits - 1] > 0 {
+        return Some((fits - 1, None))
+    }
+    // otherwise a swap with the smallest item of `light` that keeps the difference under the gap
+    for (i, x) in heavy.iter().enumerate() {
+        if i > 0 && heavy[i - 1] == *x {
+            continue
+        }
+        let j = light.partition_point(|y| (*y as u64) + gap <= *x as u64);
+        if j < light.len() && light[j] < *x {
+            return Some((i, Some(j)))
+        }
+    }
+    None
+}
+
+fn insert_sorted(bin: &mut Vec<u32>, item: u32) {
+    let position = bin.partition_point(|cals| *cals < item);
+    bin.insert(position, item);
+}
+
+// Branch and bound over bins for each item, largest first, starting from the LPT answer.
+// Bins with equal loads are interchangeable, so only one of them is tried per item.
+fn exact(items: &[u32], bins: usize, total: u64) -> Vec<Vec<u32>> {
+    let start = lpt(items, bins);
+    let mut search = Search {
+        items,
+        loads: vec![0; bins],
+        assignment: vec![0; items.len()],
+        best: start.iter().map(|bin| load(bin)).max().unwrap_or(0),
+        best_assignment: None,
+        lower: (items.first().copied().unwrap_or(0) as u64).max(total.div_ceil(bins as u64)),
+    };
+    search.assign(0, 0);
+
+    match search.best_assignment {
+        None => start,
+        Some(assignment) => {
+            let mut partition = vec![Vec::new(); bins];
+            for (item, bin) in items.iter().zip(assignment) {
+                partition[bin].push(*item);
+            }
+            partition
+        }
+    }
+}
+
+struct Search<'a> {
+    items: &'a [u32],
+    loads: Vec<u64>,
+    assignment: Vec<usize>,
+    best: u64,
+    best_assignment: Option<Vec<usize>>,
+    lower: u64,
+}
+
+impl Search<'_> {
+    fn assign(&mut self, next: usize, heaviest: u64) {
+        if self.best <= self.lower {
+            return
+        }
+        if next == self.items.len() {
+            if heaviest < self.best {
+                self.best = heaviest;
+                self.best_assignment = Some(self.assignment.clone());
+            }
+            return
+        }
+        let item = self.items[next] as u64;
+        let mut tried = Vec::new();
+        for bin in 0..self.loads.len() {
+            let load = self.loads[bin];
+            if tried.contains(&load) || load + item >= self.best {
+                continue
+            }
+            tried.push(load);
+            self.loads[bin] += item;
+            self.assignment[next] = bin;
+            self.assign(next + 1, heaviest.max(load + item));
+            self.loads[bin] -= item;
+        }
+    }
+}
+
+// Hands each bin to the elf that already holds most of its items, best overlaps first. Only elves sharing
+// an item with a bin are weighed against it; the rest are paired up in order afterwards.
+fn keep_items_in_place(original: &[Vec<u32>], partition: Vec<Vec<u32>>) -> Vec<Vec<u32>> {
+    let originals = original.iter().map(|elf| multiset::sorted(elf)).collect::<Vec<_>>();
+    let bins = partition.iter().map(|bin| multiset::sorted(bin)).collect::<Vec<_>>();
+    let mut holders = HashMap::<u32, Vec<usize>>::new();
+    for (elf, held) in originals.iter().enumerate() {
+        for (index, item) in held.iter().enumerate() {
+            if index == 0 || held[index - 1] != *item {
+                holders.entry(*item).or_default().push(elf);
+            }
+        }
+    }
+    let mut pairs = Vec::new();
+    for (bin, items) in bins.iter().enumerate() {
+        let mut elves = items.iter().filter_map(|item| holders.get(item)).flatten().copied().collect::<Vec<_>>();
+        elves.sort_unstable();
+        elves.dedup();
+        for elf in elves {
+            let kept = multiset::common(items, &originals[elf]);
+            pairs.push((Reverse(kept.len()), Reverse(load(&kept)), elf, bin));
+        }
+    }
+    pairs.sort_unstable();
+
+    let mut assigned = vec![None; original.len()];
+    let mut placed = vec![false; bins.len()];
+    for (_, _, elf, bin) in pairs {
+        if assigned[elf].is_none() && !placed[bin] {
+            assigned[elf] = Some(bin);
+            placed[bin] = true;
+        }
+    }
+    let mut unplaced = (0..bins.len()).filter(|bin| !placed[*bin]);
+    for bin in assigned.iter_mut().filter(|bin| bin.is_none()) {
+        *bin = unplaced.next();
+    }
+    // an elf's kept items stay in their old order, with whatever it was handed after them
+    assigned
+        .into_iter()
+        .zip(original)
+        .map(|(bin, held)| {
+            let mut handed = partition[bin.expect("as many bins as elves")].clone();
+            let mut items = Vec::with_capacity(handed.len());
+            for item in held {
+                if let Some(position) = handed.iter().position(|cals| cals == item) {
+                    items.push(handed.remove(position));
+                }
+            }
+            items.extend(handed);
+            items
+        })
+        .collect()
+}
+
+fn moves_between(original: &[Vec<u32>], rebalanced: &[Vec<u32>]) -> Vec<ItemMove> {
+    let mut given = Vec::new();
+    let mut taken = Vec::new();
+    for (elf, (before, after)) in original.iter().zip(rebalanced).enumerate() {
+        let (before, after) = (multiset::sorted(before), multiset::sorted(after));
+        given.extend(multiset::difference(&before, &after).into_iter().map(|cals| (cals, elf + 1)));
+        taken.extend(multiset::difference(&after, &before).into_iter().map(|cals| (cals, elf + 1)));
+    }
+    given.sort_unstable();
+    taken.sort_unstable();
+    let mut moves = given
+        .into_iter()
+        .zip(taken)
+        .map(|((calories, from), (_, to))| ItemMove { calories, from, to })
+        .collect::<Vec<_>>();
+    moves.sort_unstable_by_key(|item| (item.from, item.to, Reverse(item.calories)));
+    moves
+}
+
+impl fmt::Display for Rebalance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let solver = match self.solver {
+            Solver::Exact => "exact",
+            Solver::Heuristic => "lpt + local search",
+        };
+        writeln!(f, "solver:       {}", solver)?;
+        writeln!(f, "largest load: {} -> {}", self.max_before(), self.max_after())?;
+        writeln!(f, "items moved:  {}", self.moves.len())?;
+        for item in &self.moves {
+            writeln!(f, "  {} calories from elf {} to elf {}", item.calories, item.from, item.to)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(original: &[Vec<u32>], moves: &[ItemMove]) -> Vec<Vec<u32>> {
+        let mut inventory = original.to_vec();
+        for item in moves {
+            let from = &mut inventory[item.from - 1];
+            from.remove(from.iter().position(|cals| *cals == item.calories).unwrap());
+            inventory[item.to - 1].push(item.calories);
+        }
+        inventory.iter_mut().for_each(|elf| elf.sort_unstable());
+        inventory
+    }
+
+    #[test]
+    fn exact_solver_finds_the_optimum() {
+        // given
+        let inventory = vec![vec![3, 2, 2], vec![3, 2]];
+
+        // when
+        let actual = rebalance(&inventory, &RebalanceOptions::default()).unwrap();
+
+        // then
+        assert_eq!(Solver::Exact, actual.solver);
+        assert_eq!((7, 6), (actual.max_before(), actual.max_after()));
+        let expected = vec![ItemMove { calories: 3, from: 1, to: 2 }, ItemMove { calories: 2, from: 2, to: 1 }];
+        assert_eq!(expected, actual.moves);
+    }
+
+    #[test]
+    fn heuristic_balances_the_load() {
+        // given
+        let inventory = vec![vec![3, 3, 2, 2, 2], vec![]];
+
+        // when
+        let actual = rebalance(&inventory, &RebalanceOptions { exact_limit: 0 }).unwrap();
+
+        // then
+        assert_eq!(Solver::Heuristic, actual.solver);
+        assert_eq!(6, actual.max_after());
+        assert_eq!(vec![6, 6], actual.after);
+    }
+
+    #[test]
+    fn moves_replay_to_the_new_inventory() {
+        // given
+        let inventory = vec![vec![1, 2, 3], vec![4], vec![5, 6], vec![]];
+
+        for exact_limit in [0, 16] {
+            // when
+            let actual = rebalance(&inventory, &RebalanceOptions { exact_limit }).unwrap();
+
+            // then
+            let mut expected = actual.inventory.clone();
+            expected.iter_mut().for_each(|elf| elf.sort_unstable());
+            assert_eq!(expected, replay(&inventory, &actual.moves));
+            assert_eq!(6, actual.max_after());
+        }
+    }
+
+    #[test]
+    fn heuristic_handles_thousands_of_elves() {
+        // given: a puzzle-like inventory from a fixed linear congruential generator
+        let mut state = 2022u64;
+        let mut next = |bound: u64| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        };
+        let inventory = (0..3000)
+            .map(|_| (0..1 + next(14)).map(|_| 1000 + next(59000) as u32).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // when
+        let actual = rebalance(&inventory, &RebalanceOptions::default()).unwrap();
+        let (_, settled) = local_search(inventory.clone(), STEP_LIMIT);
+        let (_, capped) = local_search(inventory.clone(), 10);
+
+        // then: the search settles well inside its budget, and a smaller budget stops it early
+        assert!(settled < STEP_LIMIT, "took {} steps", settled);
+        assert_eq!(10, capped);
+        assert!(actual.max_after() < actual.max_before());
+        let mut expected = actual.inventory.clone();
+        expected.iter_mut().for_each(|elf| elf.sort_unstable());
+        assert_eq!(expected, replay(&inventory, &actual.moves));
+    }
+
+    #[test]
+    fn balanced_inventory_stays_put() {
+        let inventory = vec![vec![4, 1], vec![5], vec![2, 3]];
+
+        let actual = rebalance(&inventory, &RebalanceOptions::default()).unwrap();
+
+        assert!(actual.moves.is_empty());
+        assert_eq!(inventory, actual.inventory);
+    }
+}
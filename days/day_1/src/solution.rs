@@ -0,0 +1,27 @@
+use aoc_common::{Result, Solution};
+use crate::{
+    file_reader::{self, ParseMode},
+    inventory_counter::InventoryCounter,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        file_reader::inventory_from_lines(input.lines().map(|line| Ok(line.to_string())), ParseMode::Lenient)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String> {
+        let top = InventoryCounter::new(input).highest_elf_calories_held()?;
+        Ok(top.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String> {
+        let top_three = InventoryCounter::new(input).top_three_elf_calories_held()?;
+        Ok(top_three.to_string())
+    }
+}
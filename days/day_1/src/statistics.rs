@@ -0,0 +1,199 @@
+use std::fmt;
+use aoc_common::{Error, Result};
+use crate::inventory_counter::{self, Reader};
+
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// Which percentiles to report and how many histogram buckets to draw.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatisticsOptions {
+    pub percentiles: Vec<f64>,
+    pub buckets: usize,
+}
+
+impl Default for StatisticsOptions {
+    fn default() -> Self {
+        StatisticsOptions {
+            percentiles: vec![25.0, 50.0, 75.0, 90.0, 99.0],
+            buckets: 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InventoryStatistics {
+    pub elf_count: usize,
+    pub total_calories: u64,
+    pub mean: f64,
+    pub median: f64,
+    pub standard_deviation: f64,
+    /// Each requested percentile with its value, interpolated between the nearest elves.
+    pub percentiles: Vec<(f64, f64)>,
+    pub min_items: usize,
+    pub max_items: usize,
+    pub histogram: Histogram,
+}
+
+/// Elf counts per calorie range; every bucket but the last is half-open.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub start: u64,
+    pub bucket_width: u64,
+    pub counts: Vec<usize>,
+}
+
+impl InventoryStatistics {
+    pub fn from_reader<R: Reader>(reader: &R, options: &StatisticsOptions) -> Result<Self> {
+        InventoryStatistics::from_inventory(&reader.read_inventory()?, options)
+    }
+
+    pub fn from_inventory(inventory: &[Vec<u32>], options: &StatisticsOptions) -> Result<Self> {
+        if inventory.is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        if let Some(bad) = options.percentiles.iter().find(|p| !(0.0..=100.0).contains(*p)) {
+            return Err(Error::validation(format!("percentile out of range: {}", bad)))
+        }
+        if options.buckets == 0 {
+            return Err(Error::validation("a histogram needs at least one bucket"))
+        }
+
+        let mut totals = inventory
+            .iter()
+            .map(|elf| inventory_counter::elf_total(elf))
+            .collect::<Result<Vec<_>>>()?;
+        totals.sort_unstable();
+
+        let elf_count = totals.len();
+        let total_calories = totals
+            .iter()
+            .try_fold(0u64, |sum, total| sum.checked_add(*total))
+            .ok_or_else(|| Error::overflow("the inventory's calories do not fit in 64 bits"))?;
+        let mean = total_calories as f64 / elf_count as f64;
+        let variance = totals
+            .iter()
+            .map(|total| (*total as f64 - mean).powi(2))
+            .sum::<f64>() / elf_count as f64;
+        let percentiles = options
+            .percentiles
+            .iter()
+            .map(|p| (*p, percentile(&totals, *p)))
+            .collect();
+
+        Ok(InventoryStatistics {
+            elf_count,
+            total_calories,
+            mean,
+            median: percentile(&totals, 50.0),
+            standard_deviation: variance.sqrt(),
+            percentiles,
+            min_items: inventory.iter().map(Vec::len).min().unwrap_or(0),
+            max_items: inventory.iter().map(Vec::len).max().unwrap_or(0),
+            histogram: Histogram::new(&totals, options.buckets),
+        })
+    }
+}
+
+// `sorted` must be non-empty and ascending.
+fn percentile(sorted: &[u64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let fraction = rank - lower as f64;
+    sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * fraction
+}
+
+impl Histogram {
+    // `sorted` must be non-empty and ascending.
+    fn new(sorted: &[u64], buckets: usize) -> Self {
+        let start = sorted[0];
+        let end = sorted[sorted.len() - 1];
+        let bucket_width = ((end - start) / buckets as u64 + 1).max(1);
+        let mut counts = vec![0; buckets];
+        for total in sorted {
+            let bucket = (((total - start) / bucket_width) as usize).min(buckets - 1);
+            counts[bucket] += 1;
+        }
+        Histogram {
+            start,
+            bucket_width,
+            counts,
+        }
+    }
+}
+
+impl fmt::Display for Histogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let most = self.counts.iter().copied().max().unwrap_or(0).max(1);
+        let last = self.start + self.bucket_width * self.counts.len() as u64;
+        let label_width = format!("{}", last).len();
+        for (bucket, count) in self.counts.iter().enumerate() {
+            let from = self.start + self.bucket_width * bucket as u64;
+            let bar = "#".repeat((count * HISTOGRAM_WIDTH).div_ceil(most));
+            writeln!(
+                f,
+                "{:>width$} - {:>width$} | {:<bar_width$} {}",
+                from,
+                from + self.bucket_width - 1,
+                bar,
+                count,
+                width = label_width,
+                bar_width = HISTOGRAM_WIDTH
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for InventoryStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "elves:              {}", self.elf_count)?;
+        writeln!(f, "total calories:     {}", self.total_calories)?;
+        writeln!(f, "mean:               {:.1}", self.mean)?;
+        writeln!(f, "median:             {:.1}", self.median)?;
+        writeln!(f, "standard deviation: {:.1}", self.standard_deviation)?;
+        for (p, value) in &self.percentiles {
+            writeln!(f, "{:<20}{:.1}", format!("p{}:", p), value)?;
+        }
+        writeln!(f, "items per elf:      {} to {}", self.min_items, self.max_items)?;
+        writeln!(f)?;
+        write!(f, "{}", self.histogram)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_describe_inventory() {
+        // given
+        let reader = vec![vec![1, 1], vec![4], vec![2, 2, 2, 2], vec![10]];
+        let options = StatisticsOptions { percentiles: vec![0.0, 100.0], buckets: 2 };
+
+        // when
+        let actual = InventoryStatistics::from_reader(&reader, &options).unwrap();
+
+        // then
+        assert_eq!(4, actual.elf_count);
+        assert_eq!(24, actual.total_calories);
+        assert_eq!(6.0, actual.mean);
+        assert_eq!(6.0, actual.median);
+        assert!((actual.standard_deviation - 10f64.sqrt()).abs() < 1e-9);
+        assert_eq!(vec![(0.0, 2.0), (100.0, 10.0)], actual.percentiles);
+        assert_eq!((1, 4), (actual.min_items, actual.max_items));
+        assert_eq!(Histogram { start: 2, bucket_width: 5, counts: vec![2, 2] }, actual.histogram);
+    }
+
+    #[test]
+    fn percentiles_interpolate() {
+        assert_eq!(17.5, percentile(&[10, 20, 30], 37.5));
+    }
+
+    #[test]
+    fn rejects_out_of_range_percentiles() {
+        let options = StatisticsOptions { percentiles: vec![101.0], buckets: 1 };
+
+        assert!(InventoryStatistics::from_inventory(&[vec![1]], &options).is_err());
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+use aoc_common::{Error, Result};
+
+/// Elves times target calories below which the dynamic programming table is used.
+const TABLE_LIMIT: u128 = 1 << 26;
+
+/// Search nodes the branch and bound may visit before giving up.
+const SEARCH_LIMIT: u64 = 50_000_000;
+
+/// How many calories the chosen elves must hold between them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    Exactly(u64),
+    AtLeast(u64),
+}
+
+impl Target {
+    fn calories(&self) -> u64 {
+        match self {
+            Target::Exactly(calories) | Target::AtLeast(calories) => *calories,
+        }
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Target::Exactly(calories) => write!(f, "exactly {} calories", calories),
+            Target::AtLeast(calories) => write!(f, "at least {} calories", calories),
+        }
+    }
+}
+
+/// The elves picked to meet a target, by 0-based position in the inventory, in input order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subset {
+    pub indices: Vec<usize>,
+    pub total: u64,
+}
+
+/// The fewest elves whose `totals` meet `target`, or a validation error when no set of them can.
+///
+/// Exact targets too large for the table are searched for, and a search that runs out of steps before
+/// settling the answer is an [`Error::SearchExhausted`] rather than a claim that the target is unreachable.
+pub fn fewest_elves(totals: &[u64], target: Target) -> Result<Subset> {
+    if target.calories() == 0 {
+        return Err(Error::validation("the target must be at least one calorie"))
+    }
+    let unreachable = || Error::validation(format!("no set of elves holds {}", target));
+    let mut indices = match target {
+        Target::AtLeast(calories) => largest_first(totals, calories),
+        Target::Exactly(calories) if totals.len() as u128 * (calories as u128 + 1) <= TABLE_LIMIT => {
+            by_table(totals, calories)
+        }
+        Target::Exactly(calories) => by_search(totals, calories)?,
+    }
+    .ok_or_else(unreachable)?;
+
+    indices.sort_unstable();
+    let total = indices.iter().map(|index| totals[*index]).sum();
+    Ok(Subset { indices, total })
+}
+
+// Taking the biggest totals reaches any lower bound with as few elves as possible.
+fn largest_first(totals: &[u64], calories: u64) -> Option<Vec<usize>> {
+    let mut order = (0..totals.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| std::cmp::Reverse(totals[*index]));
+    let mut sum = 0u128;
+    let mut chosen = Vec::new();
+    for index in order {
+        if sum >= calories as u128 {
+            break
+        }
+        sum += totals[index] as u128;
+        chosen.push(index);
+    }
+    (sum >= calories as u128).then_some(chosen)
+}
+
+// 0/1 knapsack on the fewest elves reaching each sum, remembering which elf improved which sum so the
+// choice can be walked back. Costs elves x target in time and in bits.
+fn by_table(totals: &[u64], calories: u64) -> Option<Vec<usize>> {
+    let width = calories as usize + 1;
+    let words = width.div_ceil(64);
+    let mut fewest = vec![u32::MAX; width];
+    let mut improved = vec![0u64; totals.len() * words];
+    fewest[0] = 0;
+
+    for (elf, total) in totals.iter().enumerate() {
+        let Ok(total) = usize::try_from(*total) else { continue };
+        if total == 0 || total >= width {
+            continue
+        }
+        for sum in (total..width).rev() {
+            let from = fewest[sum - total];
+            if from != u32::MAX && from + 1 < fewest[sum] {
+                fewest[sum] = from + 1;
+                improved[elf * words + sum / 64] |= 1 << (sum % 64);
+            }
+        }
+    }
+    if fewest[calories as usize] == u32::MAX {
+        return None
+    }
+
+    // the last elf to improve a sum is the one its best count was built on
+    let mut sum = calories as usize;
+    let mut chosen = Vec::new();
+    for elf in (0..totals.len()).rev() {
+        if sum == 0 {
+            break
+        }
+        if improved[elf * words + sum / 64] >> (sum % 64) & 1 == 1 {
+            chosen.push(elf);
+            sum -= totals[elf] as usize;
+        }
+    }
+    Some(chosen)
+}
+
+// Iterative deepening on the number of elves, largest totals first, pruning any branch whose
+// largest or smallest possible completion misses the target.
+fn by_search(totals: &[u64], calories: u64) -> Result<Option<Vec<usize>>> {
+    search_within(totals, calories, SEARCH_LIMIT)
+}
+
+fn search_within(totals: &[u64], calories: u64, limit: u64) -> Result<Option<Vec<usize>>> {
+    let mut order = (0..totals.len()).filter(|index| totals[*index] > 0).collect::<Vec<_>>();
+    order.sort_by_key(|index| std::cmp::Reverse(totals[*index]));
+    let values = order.iter().map(|index| totals[*index] as u128).collect::<Vec<_>>();
+    let mut prefix = vec![0u128; values.len() + 1];
+    for (i, value) in values.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + value;
+    }
+    if prefix[values.len()] < calories as u128 {
+        return Ok(None)
+    }
+
+    let mut search = Search { values: &values, prefix: &prefix, target: calories as u128, chosen: Vec::new(), visited: 0, limit };
+    for count in 1..=values.len() {
+        if search.find(0, count, 0)? {
+            return Ok(Some(search.chosen.iter().map(|position| order[*position]).collect()))
+        }
+    }
+    Ok(None)
+}
+
+struct Search<'a> {
+    /// Largest first.
+    values: &'a [u128],
+    prefix: &'a [u128],
+    target: u128,
+    chosen: Vec<usize>,
+    visited: u64,
+    limit: u64,
+}
+
+impl Search<'_> {
+    fn find(&mut self, start: usize, left: usize, sum: u128) -> Result<bool> {
+        if left == 0 {
+            return Ok(sum == self.target)
+        }
+        self.visited += 1;
+        if self.visited > self.limit {
+            return Err(Error::search_exhausted(format!(
+                "gave up after {} steps looking for elves holding exactly {} calories",
+                self.limit, self.target
+            )))
+        }
+        let n = self.values.len();
+        let smallest = self.prefix[n] - self.prefix[n - left];
+        for i in start..=n - left {
+            if sum + smallest > self.target {
+                break
+            }
+            if i > start && self.values[i] == self.values[i - 1] {
+                continue
+            }
+            let largest = self.prefix[i + left] - self.prefix[i];
+            if sum + largest < self.target {
+                break
+            }
+            self.chosen.push(i);
+            if self.find(i + 1, left - 1, sum + self.values[i])? {
+                return Ok(true)
+            }
+            self.chosen.pop();
+        }
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_hit_an_exact_target_with_fewest_elves() {
+        // given
+        let totals = vec![6000, 4000, 11000, 24000, 10000];
+
+        // when
+        let actual = fewest_elves(&totals, Target::Exactly(21000)).unwrap();
+
+        // then
+        assert_eq!(Subset { indices: vec![2, 4], total: 21000 }, actual);
+    }
+
+    #[test]
+    fn search_agrees_with_the_table() {
+        let totals = vec![6, 4, 11, 24, 10, 3, 3, 8];
+
+        for calories in 1..=69 {
+            let table = by_table(&totals, calories);
+            let search = by_search(&totals, calories).unwrap();
+
+            let sizes = |chosen: &Option<Vec<usize>>| chosen.as_ref().map(Vec::len);
+            assert_eq!(sizes(&table), sizes(&search), "target {}", calories);
+            for chosen in table.iter().chain(search.iter()) {
+                assert_eq!(calories, chosen.iter().map(|index| totals[*index]).sum::<u64>(), "target {}", calories);
+            }
+        }
+    }
+
+    #[test]
+    fn running_out_of_steps_is_not_unreachable() {
+        let totals = vec![6, 4, 11, 24, 10, 3, 3, 8];
+
+        let actual = search_within(&totals, 69, 1).unwrap_err();
+
+        assert!(matches!(actual, Error::SearchExhausted(_)));
+    }
+
+    #[test]
+    fn at_least_takes_the_largest() {
+        let actual = fewest_elves(&[5, 30, 20, 10], Target::AtLeast(45)).unwrap();
+
+        assert_eq!(Subset { indices: vec![1, 2], total: 50 }, actual);
+    }
+
+    #[test]
+    fn unreachable_targets_are_errors() {
+        let odd = fewest_elves(&[2, 4, 6], Target::Exactly(7)).unwrap_err();
+        let too_many = fewest_elves(&[2, 4, 6], Target::AtLeast(13)).unwrap_err();
+
+        assert!(matches!(odd, Error::Validation(ref message) if message.contains("exactly 7 calories")));
+        assert!(matches!(too_many, Error::Validation(_)));
+    }
+}
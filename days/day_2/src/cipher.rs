@@ -0,0 +1,223 @@
+use std::fmt;
+use aoc_common::{Error, Input, Result};
+use crate::{
+    file_games::{self, GuideDecoder},
+    score_keeper::{Game, Outcome},
+    scoring::ScoringRules,
+    variant::{Shape, Variant},
+};
+
+/// Every way to order three things; the first is the identity.
+const ORDERS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+
+const LETTERS: [char; 3] = ['X', 'Y', 'Z'];
+
+/// What the second column of the guide is taken to say.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reading {
+    Shapes,
+    Outcomes,
+}
+
+/// One way of reading the second column: what `X`, `Y` and `Z` stand for, as shapes or as outcomes by index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cipher {
+    pub reading: Reading,
+    pub meanings: [usize; 3],
+}
+
+impl Cipher {
+    /// Part one's reading: `X`, `Y` and `Z` are rock, paper and scissors.
+    pub const PART_ONE: Cipher = Cipher { reading: Reading::Shapes, meanings: [0, 1, 2] };
+    /// Part two's reading: `X`, `Y` and `Z` are a loss, a draw and a win.
+    pub const PART_TWO: Cipher = Cipher { reading: Reading::Outcomes, meanings: [0, 1, 2] };
+
+    /// The six ways to read the letters as shapes, then the six to read them as outcomes.
+    pub fn all() -> impl Iterator<Item = Cipher> {
+        [Reading::Shapes, Reading::Outcomes]
+            .into_iter()
+            .flat_map(|reading| ORDERS.into_iter().map(move |meanings| Cipher { reading, meanings }))
+    }
+
+    // The shape this cipher has the player throw for `letter` against `opp`.
+    fn player(&self, variant: &Variant, opp: Shape, letter: usize) -> Shape {
+        match self.reading {
+            Reading::Shapes => Shape(self.meanings[letter]),
+            Reading::Outcomes => variant.answer(opp, [Outcome::Loss, Outcome::Draw, Outcome::Win][self.meanings[letter]]),
+        }
+    }
+}
+
+impl fmt::Display for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let meanings = self.meanings.iter().zip(LETTERS).map(|(meaning, letter)| {
+            let meaning = match self.reading {
+                Reading::Shapes => Variant::classic().name(Shape(*meaning)).to_string(),
+                Reading::Outcomes => ["loss", "draw", "win"][*meaning].to_string(),
+            };
+            format!("{}={}", letter, meaning)
+        });
+        write!(f, "{}", meanings.collect::<Vec<_>>().join(" "))
+    }
+}
+
+/// How the guide scores when read with one cipher, opponent's total first as in [`ScoreKeeper`](crate::score_keeper::ScoreKeeper).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CipherScore {
+    pub cipher: Cipher,
+    pub opp: u64,
+    pub player: u64,
+}
+
+/// Every cipher's score, best for the player first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CipherReport {
+    pub scores: Vec<CipherScore>,
+}
+
+impl CipherReport {
+    pub fn best(&self) -> &CipherScore {
+        &self.scores[0]
+    }
+
+    pub fn worst(&self) -> &CipherScore {
+        &self.scores[self.scores.len() - 1]
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.scores.iter().map(|score| score.player as f64).sum::<f64>() / self.scores.len() as f64
+    }
+}
+
+/// Scores a classic guide under every cipher for its second column. Only letters are read: `A`, `B` or `C`, then `X`, `Y` or `Z`.
+pub fn infer(input: &Input, rules: &ScoringRules) -> Result<CipherReport> {
+    let variant = Variant::classic();
+    if rules.variant() != &variant {
+        return Err(Error::validation(format!("ciphers are only read for rock, paper, scissors, not {}", rules.variant())))
+    }
+    let lines = input.lines()?
+        .collect::<std::io::Result<Vec<String>>>()?;
+    let games = file_games::games_from_lines(&lines, &LetterDecoder)?;
+    let mut counts = [[0u32; 3]; 3];
+    for game in &games {
+        counts[game.opp.0][game.player.0] += 1;
+    }
+
+    let overflow = || Error::overflow("a cipher's points do not fit in 64 bits");
+    let mut scores = Cipher::all()
+        .map(|cipher| {
+            let (mut opp, mut player) = (0u64, 0u64);
+            for (index, row) in counts.iter().enumerate() {
+                for (letter, count) in row.iter().enumerate() {
+                    let game = Game::from((Shape(index), cipher.player(&variant, Shape(index), letter)));
+                    let (opp_points, player_points) = rules.score(&game)?;
+                    let count = *count as u64;
+                    opp = count.checked_mul(opp_points).and_then(|points| opp.checked_add(points)).ok_or_else(overflow)?;
+                    player = count.checked_mul(player_points).and_then(|points| player.checked_add(points)).ok_or_else(overflow)?;
+                }
+            }
+            Ok(CipherScore { cipher, opp, player })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    scores.sort_by_key(|score| std::cmp::Reverse(score.player));
+    Ok(CipherReport { scores })
+}
+
+// Reads the first column strictly as `A`, `B` or `C` and the second as `X`, `Y` or `Z`, leaving each game's
+// player shape as its letter's index. Anything else, shape names included, is an error, since what the
+// letters mean is the very question.
+struct LetterDecoder;
+
+impl GuideDecoder for LetterDecoder {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        let [(opp_column, opp_raw), (letter_column, letter_raw)] = file_games::columns(line, game_raw)?;
+        let index = |raw: &str, letters: [&str; 3], column| {
+            letters
+                .iter()
+                .position(|letter| *letter == raw)
+                .map(Shape)
+                .ok_or_else(|| Error::parse(line, column, format!("expected one of {}: {}", letters.join(", "), raw)))
+        };
+        Ok((index(opp_raw, ["A", "B", "C"], opp_column)?, index(letter_raw, ["X", "Y", "Z"], letter_column)?).into())
+    }
+}
+
+impl fmt::Display for CipherReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} readings score from {} to {}, {:.1} on average",
+            self.scores.len(), self.worst().player, self.best().player, self.mean()
+        )?;
+        writeln!(f, "best: {} ({})", self.best().cipher, self.best().player)?;
+        writeln!(f, "worst: {} ({})", self.worst().cipher, self.worst().player)?;
+        for score in &self.scores {
+            let note = match score.cipher {
+                Cipher::PART_ONE => " (part one)",
+                Cipher::PART_TWO => " (part two)",
+                _ => "",
+            };
+            writeln!(f, "{:>8}  {}{}", score.player, score.cipher, note)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Input {
+        Input::Embedded("A Y\nB X\nC Z\n")
+    }
+
+    #[test]
+    fn puzzle_ciphers_give_the_puzzle_answers() {
+        // given
+        let rules = ScoringRules::default();
+
+        // when
+        let actual = infer(&example(), &rules).unwrap();
+
+        // then
+        let player = |cipher| actual.scores.iter().find(|score| score.cipher == cipher).unwrap().player;
+        assert_eq!(12, actual.scores.len());
+        assert_eq!(15, player(Cipher::PART_ONE));
+        assert_eq!(12, player(Cipher::PART_TWO));
+    }
+
+    #[test]
+    fn can_find_the_best_and_worst_readings() {
+        let actual = infer(&example(), &ScoringRules::default()).unwrap();
+
+        // every round won, then every round lost
+        assert_eq!(CipherScore { cipher: Cipher { reading: Reading::Shapes, meanings: [2, 1, 0] }, opp: 6, player: 24 }, *actual.best());
+        assert_eq!(CipherScore { cipher: Cipher { reading: Reading::Shapes, meanings: [0, 2, 1] }, opp: 24, player: 6 }, *actual.worst());
+        assert_eq!(15.0, actual.mean());
+    }
+
+    #[test]
+    fn large_points_do_not_overflow() {
+        let rules = ScoringRules::new([1, 2, 3], [0, 3, 4_000_000_000]);
+
+        let actual = infer(&example(), &rules).unwrap();
+
+        assert_eq!(12_000_000_006, actual.best().player);
+    }
+
+    #[test]
+    fn only_letters_are_read() {
+        let name = infer(&Input::Embedded("A Y\nA paper\n"), &ScoringRules::default()).unwrap_err();
+        let swapped = infer(&Input::Embedded("X A\n"), &ScoringRules::default()).unwrap_err();
+
+        assert!(matches!(name, Error::Parse { line: 2, column: 3, .. }));
+        assert!(matches!(swapped, Error::Parse { line: 1, column: 1, .. }));
+    }
+
+    #[test]
+    fn other_variants_are_rejected() {
+        let actual = infer(&example(), &ScoringRules::for_variant(Variant::rpsls())).unwrap_err();
+
+        assert!(matches!(actual, Error::Validation(_)));
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+};
+use aoc_common::{Error, Result};
+use crate::{
+    score_keeper::{score_game, Game, Games, ScoreKeeper},
+    scoring::ScoringRules,
+    variant::{Shape, Variant},
+};
+
+/// The best reply after one context of the opponent's previous shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Response {
+    /// The opponent's last shapes, oldest first; empty for the plain distribution.
+    pub context: Vec<Shape>,
+    /// How often the opponent played each shape after the context, indexed by [`Shape`].
+    pub counts: Vec<u32>,
+    pub best: Shape,
+    /// The points `best` earns a round against those counts.
+    pub expected: f64,
+}
+
+/// The score-maximizing replies to a guide's opponent, given the opponent's last `order` shapes.
+///
+/// The replies are fitted to the very rounds they are scored on, so the totals show the most that
+/// knowing the opponent's habits could have been worth, not what a bot would earn learning as it goes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CounterStrategy {
+    pub variant: Variant,
+    pub order: usize,
+    pub rounds: usize,
+    /// Replies to the opponent's plain distribution, used until `order` shapes have been seen.
+    pub opening: Response,
+    /// Replies for every context that came up, in context order.
+    pub responses: Vec<Response>,
+    /// Points for playing the replies against every round of the guide.
+    pub total: u64,
+    /// Points for the guide as written.
+    pub guide_total: u64,
+}
+
+impl CounterStrategy {
+    pub fn per_round(&self) -> f64 {
+        self.total as f64 / self.rounds as f64
+    }
+
+    pub fn guide_per_round(&self) -> f64 {
+        self.guide_total as f64 / self.rounds as f64
+    }
+
+    fn describe(&self, f: &mut fmt::Formatter<'_>, response: &Response) -> fmt::Result {
+        let seen = response.counts.iter().sum::<u32>();
+        let context = match response.context.as_slice() {
+            [] => "overall".to_string(),
+            shapes => format!("after {}", shapes.iter().map(|shape| self.variant.name(*shape)).collect::<Vec<_>>().join(",")),
+        };
+        let shares = self
+            .variant
+            .shapes()
+            .map(|shape| format!("{} {:.1}%", self.variant.name(shape), 100.0 * response.counts[shape.0] as f64 / seen.max(1) as f64))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(
+            f,
+            "{} ({} rounds): {} -> {}, {:.2} a round",
+            context, seen, shares, self.variant.name(response.best), response.expected
+        )
+    }
+}
+
+/// Builds the best replies to the opponent column of `games`, conditioning on its last `order` shapes.
+pub fn counter_strategy<G: Games>(games: &G, rules: &ScoringRules, order: usize) -> Result<CounterStrategy> {
+    let games = games.get_games()?;
+    if games.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    let variant = rules.variant();
+    for game in &games {
+        rules.check(game)?;
+    }
+    let opponent = games.iter().map(Game::opp).collect::<Vec<_>>();
+
+    let mut plain = vec![0; variant.len()];
+    let mut by_context = BTreeMap::<&[Shape], Vec<u32>>::new();
+    for (index, shape) in opponent.iter().enumerate() {
+        plain[shape.0] += 1;
+        if index >= order {
+            by_context.entry(&opponent[index - order..index]).or_insert_with(|| vec![0; variant.len()])[shape.0] += 1;
+        }
+    }
+    let opening = respond(rules, Vec::new(), plain)?;
+    let mut replies = BTreeMap::new();
+    let responses = by_context
+        .into_iter()
+        .map(|(context, counts)| {
+            let response = respond(rules, context.to_vec(), counts)?;
+            replies.insert(context, response.best);
+            Ok(response)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut total = 0u64;
+    for (index, shape) in opponent.iter().enumerate() {
+        let reply = match index.checked_sub(order) {
+            Some(start) => replies.get(&opponent[start..index]).copied().unwrap_or(opening.best),
+            None => opening.best,
+        };
+        total = total
+            .checked_add(score_game(&Game::from((*shape, reply)), rules)?.1)
+            .ok_or_else(|| Error::overflow("the counter-strategy's points do not fit in 64 bits"))?;
+    }
+    let (_, guide_total) = ScoreKeeper::with_rules(&games, rules.clone()).total_first()?;
+
+    Ok(CounterStrategy {
+        variant: variant.clone(),
+        order,
+        rounds: games.len(),
+        opening,
+        responses,
+        total,
+        guide_total,
+    })
+}
+
+// The shape with the most points against `counts`, the first of them on a tie.
+fn respond(rules: &ScoringRules, context: Vec<Shape>, counts: Vec<u32>) -> Result<Response> {
+    let seen = counts.iter().sum::<u32>().max(1) as f64;
+    let mut best = (Shape(0), f64::MIN);
+    for reply in rules.variant().shapes() {
+        let mut points = 0.0;
+        for shape in rules.variant().shapes() {
+            points += counts[shape.0] as f64 * score_game(&Game::from((shape, reply)), rules)?.1 as f64;
+        }
+        if points / seen > best.1 {
+            best = (reply, points / seen);
+        }
+    }
+    let (best, expected) = best;
+    Ok(Response { context, expected, counts, best })
+}
+
+impl fmt::Display for CounterStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.describe(f, &self.opening)?;
+        if self.order > 0 {
+            for response in &self.responses {
+                self.describe(f, response)?;
+            }
+        }
+        writeln!(f, "counter-strategy (order {}): {} points, {:.2} a round", self.order, self.total, self.per_round())?;
+        let gap = self.per_round() - self.guide_per_round();
+        writeln!(
+            f,
+            "guide as written: {} points, {:.2} a round ({:.2} a round {})",
+            self.guide_total, self.guide_per_round(), gap.abs(), if gap >= 0.0 { "behind" } else { "ahead" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_keeper::Move::{Paper, Rock};
+
+    // The opponent alternates rock and paper, and the guide always draws.
+    fn alternating() -> Vec<Game> {
+        vec![(Rock, Rock), (Paper, Paper), (Rock, Rock), (Paper, Paper)].into_iter().map(Into::into).collect()
+    }
+
+    #[test]
+    fn empirical_reply_plays_the_best_single_shape() {
+        // when
+        let actual = counter_strategy(&alternating(), &ScoringRules::default(), 0).unwrap();
+
+        // then: paper wins against rock and draws against paper
+        assert_eq!(vec![2, 2, 0], actual.opening.counts);
+        assert_eq!(Shape::from(Paper), actual.opening.best);
+        assert_eq!(6.5, actual.opening.expected);
+        assert_eq!((26, 18), (actual.total, actual.guide_total));
+    }
+
+    #[test]
+    fn conditional_replies_follow_the_pattern() {
+        // when
+        let actual = counter_strategy(&alternating(), &ScoringRules::default(), 1).unwrap();
+
+        // then: paper to open, then scissors after rock and paper after paper
+        let replies = actual.responses.iter().map(|response| (response.context.clone(), response.best)).collect::<Vec<_>>();
+        assert_eq!(vec![(vec![Rock.into()], Shape(2)), (vec![Paper.into()], Paper.into())], replies);
+        assert_eq!(8 + 9 + 8 + 9, actual.total);
+        assert_eq!(8.5, actual.per_round());
+    }
+
+    #[test]
+    fn games_outside_the_variant_are_rejected() {
+        let games = vec![Game::from((Shape(4), Shape(0)))];
+
+        let actual = counter_strategy(&games, &ScoringRules::default(), 0).unwrap_err();
+
+        assert!(matches!(actual, Error::Validation(_)));
+    }
+}
@@ -0,0 +1,17 @@
+use aoc_common::{examples, Input, Part};
+use crate::{
+    file_games::{correct::CorrectFileGames, wrong::WrongFileGames},
+    score_keeper::ScoreKeeper,
+};
+
+#[test]
+fn examples_match_expected_answers() {
+    examples::check_examples(2, |path, part| {
+        let input = Input::File(path.to_path_buf());
+        let (_, player) = match part {
+            Part::One => ScoreKeeper::new(WrongFileGames::new(input)).total_first()?,
+            Part::Two => ScoreKeeper::new(CorrectFileGames::new(input)).total_first()?,
+        };
+        Ok(player.to_string())
+    });
+}
@@ -1,17 +1,66 @@
-use std::{
-    fs::File,
-    io,
-    io::BufRead,
-    path::Path
-};
+use aoc_common::{Error, Input, Result};
+use crate::score_keeper::{Game, Games};
 
 pub mod wrong;
 pub mod correct;
+pub mod cyclic;
+pub mod table;
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
+/// One way of reading a line of a strategy guide.
+pub trait GuideDecoder {
+    /// Decodes the guide's `line`th line, counting from 1.
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game>;
+}
+
+impl<D: GuideDecoder + ?Sized> GuideDecoder for &D {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        (**self).decode(line, game_raw)
+    }
+}
+
+/// A strategy guide file, read a line at a time by its decoder.
+pub struct FileGames<D: GuideDecoder> {
+    input: Input,
+    decoder: D,
+}
+
+impl<D: GuideDecoder> FileGames<D> {
+    pub fn with_decoder(input: Input, decoder: D) -> Self {
+        FileGames { input, decoder }
+    }
+}
+
+impl<D: GuideDecoder> Games for FileGames<D> {
+    fn get_games(&self) -> Result<Vec<Game>> {
+        let lines = self.input.lines()?
+            .collect::<std::io::Result<Vec<String>>>()?;
+        games_from_lines(&lines, &self.decoder)
+    }
+}
+
+/// Decodes every line of a strategy guide, numbering lines from 1.
+pub(crate) fn games_from_lines<S, D>(lines: &[S], decoder: &D) -> Result<Vec<Game>>
     where
-        P: AsRef<Path>,
+        S: AsRef<str>,
+        D: GuideDecoder + ?Sized,
 {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
\ No newline at end of file
+    let games = lines
+        .iter()
+        .enumerate()
+        .map(|(index, game_raw)| decoder.decode(index + 1, game_raw.as_ref()))
+        .collect::<Result<Vec<Game>>>()?;
+    if games.is_empty() {
+        return Err(Error::EmptyInput)
+    }
+    Ok(games)
+}
+
+/// Splits a guide line into its two columns, each paired with its 1-based column number.
+pub(crate) fn columns(line: usize, game_raw: &str) -> Result<[(usize, &str); 2]> {
+    let column_of = |token: &str| token.as_ptr() as usize - game_raw.as_ptr() as usize + 1;
+    let malformed = || Error::parse(line, game_raw.len() + 1, format!("Game string malformed: {}", game_raw));
+    let mut moves_raw = game_raw.split_whitespace();
+    let first = moves_raw.next().ok_or_else(malformed)?;
+    let second = moves_raw.next().ok_or_else(malformed)?;
+    Ok([(column_of(first), first), (column_of(second), second)])
+}
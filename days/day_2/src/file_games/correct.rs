@@ -1,18 +1,18 @@
 use std::str::FromStr;
+use aoc_common::{Error, Input, Result};
 use crate::{
     score_keeper::{
         Game,
-        Games,
         Move::{
             self,
             Paper,
             Rock,
             Scissors
-        }
+        },
+        Outcome,
     },
-    error::{error_to_string, MyError},
-    error,
-    file_games,
+    file_games::{self, FileGames, GuideDecoder},
+    variant::Shape,
 };
 
 pub enum GameResult {
@@ -22,62 +22,63 @@ pub enum GameResult {
 }
 
 impl FromStr for GameResult {
-    type Err = MyError;
+    type Err = String;
 
-    fn from_str(s: &str) -> error::Result<Self, Self::Err> {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "X" => Ok(GameResult::Lose),
             "Y" => Ok(GameResult::Draw),
             "Z" => Ok(GameResult::Win),
-            _ => Err(format!("Not a valid move: {}", s))
+            _ => Err(format!("Not a valid result: {}", s))
         }
     }
 }
 
-pub struct CorrectFileGames {
-    file_path: String
+impl From<GameResult> for Outcome {
+    fn from(result: GameResult) -> Self {
+        match result {
+            GameResult::Lose => Outcome::Loss,
+            GameResult::Draw => Outcome::Draw,
+            GameResult::Win => Outcome::Win,
+        }
+    }
 }
 
-impl CorrectFileGames {
-    pub fn new(file_path: String) -> Self {
-        CorrectFileGames { file_path }
+/// Reads the second column as the outcome to play for, as part two does: `X` lose, `Y` draw and `Z` win.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OutcomeDecoder;
+
+impl GuideDecoder for OutcomeDecoder {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        correct_game_from_string(line, game_raw)
     }
 }
 
-impl Games for CorrectFileGames {
-    fn get_games(&self) -> error::Result<Vec<Game>> {
-        file_games::read_lines(&self.file_path)
-            .map_err(error_to_string)?
-            .map(|maybe_e| maybe_e.map_err(error_to_string))
-            .collect::<error::Result<Vec<String>>>()?
-            .iter()
-            .map(|game_raw| correct_game_from_string(&game_raw))
-            .collect()
+pub type CorrectFileGames = FileGames<OutcomeDecoder>;
+
+impl CorrectFileGames {
+    pub fn new(input: Input) -> Self {
+        FileGames::with_decoder(input, OutcomeDecoder)
     }
 }
 
-fn correct_game_from_string(game_raw: &str) -> error::Result<Game> {
-    let mut moves_raw = game_raw.split_whitespace();
-    let opp_raw = moves_raw.next().ok_or(format!("Game string malformed: {}", game_raw))?;
-    let result_raw = moves_raw.next().ok_or(format!("Game string malformed: {}", game_raw))?;
-    let opp = correct_move_from_str(opp_raw)?;
-    let result = GameResult::from_str(result_raw)?;
-    let player = match (opp, result) {
-        (Rock, GameResult::Lose) => Scissors,
-        (Rock, GameResult::Draw) => Rock,
-        (Rock, GameResult::Win) => Paper,
-        (Paper, GameResult::Lose) => Rock,
-        (Paper, GameResult::Draw) => Paper,
-        (Paper, GameResult::Win) => Scissors,
-        (Scissors, GameResult::Lose) => Paper,
-        (Scissors, GameResult::Draw) => Scissors,
-        (Scissors, GameResult::Win) => Rock,
+pub(crate) fn correct_game_from_string(line: usize, game_raw: &str) -> Result<Game> {
+    let [(opp_column, opp_raw), (result_column, result_raw)] = file_games::columns(line, game_raw)?;
+    let opp = correct_move_from_str(opp_raw)
+        .map_err(|message| Error::parse(line, opp_column, message))?;
+    let result = GameResult::from_str(result_raw)
+        .map_err(|message| Error::parse(line, result_column, message))?;
+    // the classic game's answer, worked out here rather than building its variant for every line
+    let offset = match result {
+        GameResult::Lose => 2,
+        GameResult::Draw => 0,
+        GameResult::Win => 1,
     };
-    let game = (opp, player).into();
+    let game = (Shape::from(opp), Shape((opp as usize + offset) % 3)).into();
     Ok(game)
 }
 
-fn correct_move_from_str(s: &str) -> error::Result<Move> {
+fn correct_move_from_str(s: &str) -> Result<Move, String> {
     match s {
         "A" => Ok(Rock),
         "B" => Ok(Paper),
@@ -86,4 +87,17 @@ fn correct_move_from_str(s: &str) -> error::Result<Move> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_result_reports_its_position() {
+        let actual = correct_game_from_string(7, "A  Q").unwrap_err();
 
+        match actual {
+            Error::Parse { line, column, .. } => assert_eq!((7, 4), (line, column)),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
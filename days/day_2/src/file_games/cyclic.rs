@@ -0,0 +1,103 @@
+use aoc_common::{Error, Input, Result};
+use crate::{
+    file_games::{self, FileGames, GuideDecoder},
+    score_keeper::Game,
+    variant::{Shape, Variant},
+};
+
+/// Variants with more shapes than this have no letters at all, and every shape is written by name.
+const LETTERED_SHAPES: usize = 13;
+
+/// Reads both columns as shapes of any [`Variant`].
+///
+/// A shape is written by its name in any case, or by a letter: the opponent's column counts from `A`,
+/// and the player's column takes the last letters of the alphabet, so the classic game reads exactly
+/// like the puzzle's `A Y` lines, and rock-paper-scissors-spock-lizard uses `A`-`E` and `V`-`Z`.
+#[derive(Clone, Debug)]
+pub struct CyclicDecoder {
+    variant: Variant,
+}
+
+impl CyclicDecoder {
+    pub fn new(variant: Variant) -> Self {
+        CyclicDecoder { variant }
+    }
+}
+
+impl GuideDecoder for CyclicDecoder {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        cyclic_game_from_string(&self.variant, line, game_raw)
+    }
+}
+
+pub type CyclicFileGames = FileGames<CyclicDecoder>;
+
+impl CyclicFileGames {
+    pub fn new(input: Input, variant: Variant) -> Self {
+        FileGames::with_decoder(input, CyclicDecoder::new(variant))
+    }
+}
+
+pub(crate) fn cyclic_game_from_string(variant: &Variant, line: usize, game_raw: &str) -> Result<Game> {
+    let [(opp_column, opp_raw), (player_column, player_raw)] = file_games::columns(line, game_raw)?;
+    let opp = shape_from_str(variant, opp_raw, false)
+        .ok_or_else(|| Error::parse(line, opp_column, format!("Not a valid move: {}", opp_raw)))?;
+    let player = shape_from_str(variant, player_raw, true)
+        .ok_or_else(|| Error::parse(line, player_column, format!("Not a valid move: {}", player_raw)))?;
+    Ok((opp, player).into())
+}
+
+// A letter wins over a one-letter name, so the names are only looked up when the letter is not in range.
+// The opponent's letters count from `A`, and the player's, `up_to_z`, end at `Z`.
+fn shape_from_str(variant: &Variant, s: &str, up_to_z: bool) -> Option<Shape> {
+    let lettered = match s.as_bytes() {
+        [letter] if variant.len() <= LETTERED_SHAPES => letter
+            .checked_sub(if up_to_z { b'Z' + 1 - variant.len() as u8 } else { b'A' })
+            .map(|index| Shape(index as usize))
+            .filter(|shape| variant.contains(*shape)),
+        _ => None,
+    };
+    lettered.or_else(|| variant.shape(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_games::wrong::wrong_game_from_string;
+
+    #[test]
+    fn classic_letters_read_like_the_puzzle() {
+        for game_raw in ["A X", "A Y", "A Z", "B X", "B Y", "B Z", "C X", "C Y", "C Z"] {
+            let expected = wrong_game_from_string(1, game_raw).unwrap();
+
+            let actual = cyclic_game_from_string(&Variant::classic(), 1, game_raw).unwrap();
+
+            assert_eq!(expected, actual, "{}", game_raw);
+        }
+    }
+
+    #[test]
+    fn can_read_letters_and_names_for_larger_variants() {
+        // given
+        let variant = Variant::rpsls();
+
+        // when
+        let letters = cyclic_game_from_string(&variant, 1, "E V").unwrap();
+        let names = cyclic_game_from_string(&variant, 2, "Lizard rock").unwrap();
+        let bad = cyclic_game_from_string(&variant, 4, "F Z").unwrap_err();
+
+        // then
+        assert_eq!(letters, names);
+        assert_eq!(Game::from((Shape(4), Shape(0))), names);
+        assert!(matches!(bad, Error::Parse { line: 4, column: 1, .. }));
+    }
+
+    #[test]
+    fn one_letter_names_are_read_when_no_letter_matches() {
+        let variant = "a,b,c".parse::<Variant>().unwrap();
+
+        let actual = cyclic_game_from_string(&variant, 1, "c a").unwrap();
+
+        assert_eq!(Game::from((Shape(2), Shape(0))), actual);
+    }
+}
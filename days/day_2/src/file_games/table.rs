@@ -0,0 +1,159 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+};
+use aoc_common::{sections, Error, Result};
+use crate::{
+    file_games::{self, GuideDecoder},
+    score_keeper::{Game, Outcome},
+    variant::{Shape, Variant},
+};
+
+/// What a player's token asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Meaning {
+    Shape(Shape),
+    Outcome(Outcome),
+}
+
+/// Reads a guide through mapping tables from a config file, for guides written in some other code.
+///
+/// The opponent's tokens name shapes, and the player's tokens each name a shape or the outcome to play for:
+///
+/// ```toml
+/// [opponent]
+/// A = "rock"
+/// B = "paper"
+/// C = "scissors"
+///
+/// [player]
+/// X = "scissors"
+/// Y = "draw"
+/// Z = "win"
+/// ```
+///
+/// Tokens match exactly; shape and outcome names ignore case.
+#[derive(Clone, Debug)]
+pub struct TableDecoder {
+    variant: Variant,
+    opponent: HashMap<String, Shape>,
+    player: HashMap<String, Meaning>,
+}
+
+impl TableDecoder {
+    pub fn load(path: &Path, variant: Variant) -> Result<Self> {
+        TableDecoder::parse(&fs::read_to_string(path)?, variant)
+    }
+
+    pub fn parse(raw: &str, variant: Variant) -> Result<Self> {
+        let mut opponent = HashMap::new();
+        let mut player = HashMap::new();
+        for entry in sections::entries(raw)? {
+            let (token, value) = (entry.key, entry.value);
+            let unknown = |what: &str| Error::parse(entry.line, entry.value_column, format!("not a {}: {}", what, value));
+            let repeated = |section: &str| Error::parse(entry.line, 1, format!("token mapped twice in [{}]: {}", section, token));
+            match entry.section {
+                Some("opponent") => {
+                    let shape = variant.shape(value).ok_or_else(|| unknown("shape"))?;
+                    if opponent.insert(token.to_string(), shape).is_some() {
+                        return Err(repeated("opponent"))
+                    }
+                }
+                Some("player") => {
+                    let meaning = match (variant.shape(value), outcome_from_str(value)) {
+                        (Some(shape), _) => Meaning::Shape(shape),
+                        (None, Some(outcome)) => Meaning::Outcome(outcome),
+                        (None, None) => return Err(unknown("shape or outcome")),
+                    };
+                    if player.insert(token.to_string(), meaning).is_some() {
+                        return Err(repeated("player"))
+                    }
+                }
+                Some(other) => return Err(Error::parse(entry.line, 1, format!("unknown section: {}", other))),
+                None => return Err(Error::parse(entry.line, 1, "tokens outside of [opponent] or [player]")),
+            }
+        }
+        if opponent.is_empty() || player.is_empty() {
+            return Err(Error::validation("a mapping table needs [opponent] and [player] tokens"))
+        }
+        Ok(TableDecoder { variant, opponent, player })
+    }
+}
+
+impl GuideDecoder for TableDecoder {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        let [(opp_column, opp_raw), (player_column, player_raw)] = file_games::columns(line, game_raw)?;
+        let opp = *self
+            .opponent
+            .get(opp_raw)
+            .ok_or_else(|| Error::parse(line, opp_column, format!("Not a valid move: {}", opp_raw)))?;
+        let player = match self.player.get(player_raw) {
+            Some(Meaning::Shape(shape)) => *shape,
+            Some(Meaning::Outcome(outcome)) => self.variant.answer(opp, *outcome),
+            None => return Err(Error::parse(line, player_column, format!("Not a valid move or result: {}", player_raw))),
+        };
+        Ok((opp, player).into())
+    }
+}
+
+fn outcome_from_str(s: &str) -> Option<Outcome> {
+    match s.to_lowercase().as_str() {
+        "loss" | "lose" => Some(Outcome::Loss),
+        "draw" => Some(Outcome::Draw),
+        "win" => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_games::{correct::OutcomeDecoder, wrong::ShapeDecoder};
+
+    const EXAMPLE: [&str; 3] = ["A Y", "B X", "C Z"];
+
+    #[test]
+    fn tables_can_repeat_the_puzzle_readings() {
+        // given
+        let shapes = "[opponent]\nA = rock\nB = paper\nC = scissors\n\n[player]\nX = rock\nY = paper\nZ = scissors\n";
+        let outcomes = "[opponent]\nA = \"rock\"\nB = \"paper\"\nC = \"scissors\"\n\n[player]\nX = \"lose\"\nY = \"draw\"\nZ = \"win\"\n";
+
+        // when
+        let by_shapes = file_games::games_from_lines(&EXAMPLE, &TableDecoder::parse(shapes, Variant::classic()).unwrap());
+        let by_outcomes = file_games::games_from_lines(&EXAMPLE, &TableDecoder::parse(outcomes, Variant::classic()).unwrap());
+
+        // then
+        assert_eq!(file_games::games_from_lines(&EXAMPLE, &ShapeDecoder).unwrap(), by_shapes.unwrap());
+        assert_eq!(file_games::games_from_lines(&EXAMPLE, &OutcomeDecoder).unwrap(), by_outcomes.unwrap());
+    }
+
+    #[test]
+    fn tables_can_mix_shapes_and_outcomes() {
+        let table = TableDecoder::parse("[opponent]\nR = rock\n[player]\nl = lizard\nw = win\n", Variant::rpsls()).unwrap();
+
+        let actual = file_games::games_from_lines(&["R l", "R w"], &table).unwrap();
+
+        let shape = |name| Variant::rpsls().shape(name).unwrap();
+        assert_eq!(vec![Game::from((shape("rock"), shape("lizard"))), Game::from((shape("rock"), shape("paper")))], actual);
+    }
+
+    #[test]
+    fn unknown_names_and_tokens_report_their_position() {
+        let name = TableDecoder::parse("[opponent]\nA = rock\n[player]\nX =  lizard\n", Variant::classic()).unwrap_err();
+        let table = TableDecoder::parse("[opponent]\nA = rock\n[player]\nX = win\n", Variant::classic()).unwrap();
+        let token = table.decode(3, "A Y").unwrap_err();
+
+        assert!(matches!(name, Error::Parse { line: 4, column: 6, .. }));
+        assert!(matches!(token, Error::Parse { line: 3, column: 3, .. }));
+    }
+
+    #[test]
+    fn repeated_tokens_are_rejected() {
+        let opponent = TableDecoder::parse("[opponent]\nA = rock\nA = paper\n[player]\nX = win\n", Variant::classic()).unwrap_err();
+        let player = TableDecoder::parse("[opponent]\nA = rock\n[player]\nX = win\n\"X\" = draw\n", Variant::classic()).unwrap_err();
+
+        assert!(matches!(opponent, Error::Parse { line: 3, column: 1, .. }));
+        assert!(matches!(player, Error::Parse { line: 5, column: 1, .. }));
+    }
+}
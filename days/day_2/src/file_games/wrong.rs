@@ -1,45 +1,43 @@
-use crate::{error, file_games};
-use crate::error::error_to_string;
-use crate::score_keeper::{Game, Games, Move};
+use aoc_common::{Error, Input, Result};
+use crate::file_games::{self, FileGames, GuideDecoder};
+use crate::score_keeper::{Game, Move};
 use crate::score_keeper::Move::{Paper, Rock, Scissors};
 
-pub struct WrongFileGames {
-    file_path: String
-}
+/// Reads both columns as shapes, as part one does: `A`/`X` rock, `B`/`Y` paper and `C`/`Z` scissors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShapeDecoder;
 
-impl WrongFileGames {
-    pub fn new(file_path: String) -> Self {
-        WrongFileGames { file_path }
+impl GuideDecoder for ShapeDecoder {
+    fn decode(&self, line: usize, game_raw: &str) -> Result<Game> {
+        wrong_game_from_string(line, game_raw)
     }
 }
 
-impl Games for WrongFileGames {
-    fn get_games(&self) -> error::Result<Vec<Game>> {
-         file_games::read_lines(&self.file_path)
-            .map_err(error_to_string)?
-            .map(|maybe_e| maybe_e.map_err(error_to_string))
-            .collect::<error::Result<Vec<String>>>()?
-             .iter()
-             .map(|game_raw| wrong_game_from_string(&game_raw))
-             .collect()
+pub type WrongFileGames = FileGames<ShapeDecoder>;
+
+impl WrongFileGames {
+    pub fn new(input: Input) -> Self {
+        FileGames::with_decoder(input, ShapeDecoder)
     }
 }
 
-fn wrong_game_from_string(game_raw: &str) -> error::Result<Game> {
-    let mut moves_raw = game_raw.split_whitespace();
-        let opp_raw = moves_raw.next().ok_or(format!("Game string malformed: {}", game_raw))?;
-        let player_raw = moves_raw.next().ok_or(format!("Game string malformed: {}", game_raw))?;
-        let opp = wrong_move_from_str(opp_raw)?;
-        let player = wrong_move_from_str(player_raw)?;
-        let game = (opp, player).into();
-        Ok(game)
+pub(crate) fn wrong_game_from_string(line: usize, game_raw: &str) -> Result<Game> {
+    let [(opp_column, opp_raw), (player_column, player_raw)] = file_games::columns(line, game_raw)?;
+    let opp = wrong_move_from_str(opp_raw).ok_or_else(|| invalid_move(line, opp_column, opp_raw))?;
+    let player = wrong_move_from_str(player_raw).ok_or_else(|| invalid_move(line, player_column, player_raw))?;
+    let game = (opp, player).into();
+    Ok(game)
 }
 
-fn wrong_move_from_str(s: &str) -> error::Result<Move> {
+fn wrong_move_from_str(s: &str) -> Option<Move> {
     match s {
-            "A" | "X" => Ok(Rock),
-            "B" | "Y" => Ok(Paper),
-            "C" | "Z" => Ok(Scissors),
-            _ => Err(format!("Not a valid move: {}", s))
-        }
+        "A" | "X" => Some(Rock),
+        "B" | "Y" => Some(Paper),
+        "C" | "Z" => Some(Scissors),
+        _ => None
+    }
+}
+
+fn invalid_move(line: usize, column: usize, s: &str) -> Error {
+    Error::parse(line, column, format!("Not a valid move: {}", s))
 }
@@ -0,0 +1,13 @@
+pub mod score_keeper;
+pub mod scoring;
+pub mod cipher;
+pub mod counter;
+pub mod file_games;
+pub mod solution;
+pub mod tournament;
+pub mod variant;
+
+#[cfg(test)]
+mod examples;
+
+pub const DATA: &str = include_str!("../../../data/day_2.txt");
@@ -1,80 +1,114 @@
-use crate::error;
+use aoc_common::{Error, Result};
+use crate::{
+    scoring::ScoringRules,
+    variant::Shape,
+};
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Move {
     Rock,
     Paper,
     Scissors,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+/// One round; the shapes belong to whichever [`Variant`](crate::variant::Variant) the round is scored under.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Game {
-    opp: Move,
-    player: Move,
+    pub(crate) opp: Shape,
+    pub(crate) player: Shape,
+}
+
+impl Game {
+    pub fn opp(&self) -> Shape {
+        self.opp
+    }
+
+    pub fn player(&self) -> Shape {
+        self.player
+    }
 }
 
 impl From<(Move, Move)> for Game {
     fn from((opp, player): (Move, Move)) -> Self {
+        Game { opp: opp.into(), player: player.into() }
+    }
+}
+
+impl From<(Shape, Shape)> for Game {
+    fn from((opp, player): (Shape, Shape)) -> Self {
         Game { opp, player }
     }
 }
 
 pub trait Games {
-    fn get_games(&self) -> error::Result<Vec<Game>>;
+    fn get_games(&self) -> Result<Vec<Game>>;
+}
+
+impl Games for Vec<Game> {
+    fn get_games(&self) -> Result<Vec<Game>> {
+        let games = self.to_owned();
+        Ok(games)
+    }
+}
+
+impl<G: Games + ?Sized> Games for &G {
+    fn get_games(&self) -> Result<Vec<Game>> {
+        (**self).get_games()
+    }
 }
 
 pub struct ScoreKeeper<G: Games> {
     games: G,
+    rules: ScoringRules,
 }
 
 impl<G: Games> ScoreKeeper<G> {
+    /// Scores by the puzzle's rules.
     pub fn new(choices: G) -> Self {
-        ScoreKeeper { games: choices }
+        ScoreKeeper::with_rules(choices, ScoringRules::default())
+    }
+
+    pub fn with_rules(choices: G, rules: ScoringRules) -> Self {
+        ScoreKeeper { games: choices, rules }
     }
 
-    pub fn total_first(&self) -> error::Result<(u32, u32)> {
-        let totals = self
-            .games
+    /// Sums in 64 bits; a total that still does not fit is an error, never a wrap.
+    /// Games with shapes from another variant than the rules' are rejected too.
+    pub fn total_first(&self) -> Result<(u64, u64)> {
+        self.games
             .get_games()?
             .iter()
-            .map(score_game)
-            .fold((0, 0), |(opp_total, player_total), (opp, player)| {
-                (opp_total + opp, player_total + player)
-            });
-        Ok(totals)
+            .try_fold((0u64, 0u64), |(opp_total, player_total), game| {
+                let (opp, player) = score_game(game, &self.rules)?;
+                let overflow = || Error::overflow("the guide's points do not fit in 64 bits");
+                Ok((opp_total.checked_add(opp).ok_or_else(overflow)?, player_total.checked_add(player).ok_or_else(overflow)?))
+            })
     }
 }
 
-fn score_game(game: &Game) -> (u32, u32) {
-    let Game { opp, player } = game;
-    match (opp, player) {
-        (Move::Rock, Move::Rock) => (4, 4),
-        (Move::Rock, Move::Paper) => (1, 8),
-        (Move::Rock, Move::Scissors) => (7, 3),
-        (Move::Paper, Move::Rock) => (8, 1),
-        (Move::Paper, Move::Paper) => (5, 5),
-        (Move::Paper, Move::Scissors) => (2, 9),
-        (Move::Scissors, Move::Rock) => (3, 7),
-        (Move::Scissors, Move::Paper) => (9, 2),
-        (Move::Scissors, Move::Scissors) => (6, 6),
-    }
+/// Both players' points for one round, opponent first.
+pub fn score_game(game: &Game, rules: &ScoringRules) -> Result<(u64, u64)> {
+    rules.score(game)
 }
 
 #[cfg(test)]
 mod tests {
+    use aoc_common::{Error, Input};
     use crate::{
-        error,
-        score_keeper::{Game, Games, ScoreKeeper},
-        score_keeper::Move::{Paper, Rock, Scissors}
+        file_games::cyclic::CyclicFileGames,
+        score_keeper::{Game, ScoreKeeper},
+        scoring::ScoringRules,
+        score_keeper::Move::{Paper, Rock, Scissors},
+        variant::Variant,
     };
 
-    impl Games for Vec<Game> {
-        fn get_games(&self) -> error::Result<Vec<Game>> {
-            let games = self.to_owned();
-            Ok(games)
-        }
-    }
-
     #[test]
     fn can_total_scores() {
         let games: Vec<Game> = vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)]
@@ -90,4 +124,45 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn can_total_scores_with_other_rules() {
+        // given
+        let games: Vec<Game> = vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)]
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let rules = ScoringRules::new([0, 0, 0], [0, 1, 2]);
+
+        // when
+        let actual = ScoreKeeper::with_rules(games, rules).total_first().unwrap();
+
+        // then
+        assert_eq!((3, 3), actual);
+    }
+
+    #[test]
+    fn large_points_do_not_overflow() {
+        // given
+        let games: Vec<Game> = vec![(Rock, Paper), (Rock, Paper)].into_iter().map(Into::into).collect();
+        let rules = ScoringRules::new([1, 2, 3], [0, 3, 4_000_000_000]);
+
+        // when
+        let actual = ScoreKeeper::with_rules(games, rules).total_first().unwrap();
+
+        // then
+        assert_eq!((2, 8_000_000_004), actual);
+    }
+
+    #[test]
+    fn games_from_a_bigger_variant_are_rejected() {
+        // given: lizard against spock
+        let games = CyclicFileGames::new(Input::Embedded("E Y\n"), Variant::rpsls());
+
+        // when
+        let actual = ScoreKeeper::new(games).total_first().unwrap_err();
+
+        // then
+        assert!(matches!(actual, Error::Validation(_)));
+    }
 }
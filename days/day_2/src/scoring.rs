@@ -0,0 +1,210 @@
+use std::{
+    fs,
+    path::Path,
+};
+use aoc_common::{json::Cursor, sections, Error, Result};
+use crate::{
+    score_keeper::{Game, Outcome},
+    variant::{Shape, Variant},
+};
+
+/// Points for the shape each player picks and for how the round ends for them.
+///
+/// Rules files name the points in two sections, and anything left out keeps the puzzle's value:
+///
+/// ```toml
+/// [shapes]
+/// rock = 1
+/// paper = 2
+/// scissors = 3
+///
+/// [outcomes]
+/// loss = 0
+/// draw = 3
+/// win = 6
+/// ```
+///
+/// The same can be written as JSON: `{"shapes": {"rock": 1}, "outcomes": {"win": 10}}`.
+/// Other variants name their own shapes, which are worth 1, 2, 3 and so on in order unless a file says otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoringRules {
+    variant: Variant,
+    /// Indexed by [`Shape`].
+    shapes: Vec<u32>,
+    /// Indexed by [`Outcome`]: loss, draw, win.
+    outcomes: [u32; 3],
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules::for_variant(Variant::classic())
+    }
+}
+
+impl ScoringRules {
+    /// Points for rock, paper and scissors, then for a loss, a draw and a win.
+    pub fn new(shapes: [u32; 3], outcomes: [u32; 3]) -> Self {
+        ScoringRules { variant: Variant::classic(), shapes: shapes.to_vec(), outcomes }
+    }
+
+    pub fn for_variant(variant: Variant) -> Self {
+        let shapes = (1..=variant.len() as u32).collect();
+        ScoringRules { variant, shapes, outcomes: [0, 3, 6] }
+    }
+
+    /// Reads JSON when the file ends in `.json`, and TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        ScoringRules::load_for(Variant::classic(), path)
+    }
+
+    pub fn load_for(variant: Variant, path: &Path) -> Result<Self> {
+        let raw = fs::read_to_string(path)?;
+        let rules = ScoringRules::for_variant(variant);
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => rules.read_json(&raw),
+            _ => rules.read_toml(&raw),
+        }
+    }
+
+    pub fn parse_toml(raw: &str) -> Result<Self> {
+        ScoringRules::default().read_toml(raw)
+    }
+
+    pub fn parse_json(raw: &str) -> Result<Self> {
+        ScoringRules::default().read_json(raw)
+    }
+
+    fn read_toml(mut self, raw: &str) -> Result<Self> {
+        for entry in sections::entries(raw)? {
+            let section = entry.section.ok_or_else(|| Error::parse(entry.line, 1, "points outside of [shapes] or [outcomes]"))?;
+            let points = entry
+                .value
+                .parse::<u32>()
+                .map_err(|_| Error::parse(entry.line, entry.value_column, format!("not a number of points: {}", entry.value)))?;
+            self.set(section, entry.key, points).map_err(|message| Error::parse(entry.line, 1, message))?;
+        }
+        Ok(self)
+    }
+
+    fn read_json(mut self, raw: &str) -> Result<Self> {
+        let mut cursor = Cursor::new(raw);
+        cursor.object(|cursor, section| {
+            let section = section.to_string();
+            cursor.object(|cursor, key| {
+                let (line, column) = (cursor.line(), cursor.column());
+                let points = cursor.number("a number of points")?;
+                self.set(&section, key, points).map_err(|message| Error::parse(line, column, message))
+            })
+        })?;
+        cursor.end("rules")?;
+        Ok(self)
+    }
+
+    fn set(&mut self, section: &str, key: &str, points: u32) -> Result<(), String> {
+        let slot = match (section, key) {
+            ("shapes", name) => match self.variant.shape(name) {
+                Some(shape) => &mut self.shapes[shape.0],
+                None => return Err(format!("unknown shapes entry: {}", name)),
+            },
+            ("outcomes", "loss") => &mut self.outcomes[0],
+            ("outcomes", "draw") => &mut self.outcomes[1],
+            ("outcomes", "win") => &mut self.outcomes[2],
+            ("outcomes", other) => return Err(format!("unknown outcomes entry: {}", other)),
+            (other, _) => return Err(format!("unknown section: {}", other)),
+        };
+        *slot = points;
+        Ok(())
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    pub fn shape_points(&self, shape: Shape) -> u32 {
+        self.shapes[shape.0]
+    }
+
+    pub fn outcome_points(&self, outcome: Outcome) -> u32 {
+        self.outcomes[outcome as usize]
+    }
+
+    /// Errors unless both of `game`'s shapes belong to these rules' variant.
+    pub fn check(&self, game: &Game) -> Result<()> {
+        match [game.opp, game.player].into_iter().find(|shape| !self.variant.contains(*shape)) {
+            Some(shape) => Err(Error::validation(format!("shape {} is not in {}", shape.0, self.variant))),
+            None => Ok(()),
+        }
+    }
+
+    /// Both players' points for one round, opponent first; two `u32` points always fit in a `u64`.
+    pub fn score(&self, game: &Game) -> Result<(u64, u64)> {
+        self.check(game)?;
+        let points = |shape, other| self.shape_points(shape) as u64 + self.outcome_points(self.variant.outcome(shape, other)) as u64;
+        Ok((points(game.opp, game.player), points(game.player, game.opp)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_keeper::Move::{Paper, Rock, Scissors};
+
+    #[test]
+    fn default_rules_are_the_puzzle_rules() {
+        let rules = ScoringRules::default();
+
+        assert_eq!((1, 8), rules.score(&(Rock, Paper).into()).unwrap());
+        assert_eq!((6, 6), rules.score(&(Scissors, Scissors).into()).unwrap());
+        assert_eq!((9, 2), rules.score(&(Scissors, Paper).into()).unwrap());
+    }
+
+    #[test]
+    fn can_parse_toml_rules() {
+        // given
+        let raw = "# house rules\n[shapes]\nrock = 5\n\n[outcomes]\nwin = 10  # doubled\n";
+
+        // when
+        let actual = ScoringRules::parse_toml(raw).unwrap();
+
+        // then
+        assert_eq!(ScoringRules::new([5, 2, 3], [0, 3, 10]), actual);
+    }
+
+    #[test]
+    fn can_parse_json_rules() {
+        let raw = "{\n  \"shapes\": {\"rock\": 5},\n  \"outcomes\": {\"loss\": 1, \"win\": 10}\n}\n";
+
+        let actual = ScoringRules::parse_json(raw).unwrap();
+
+        assert_eq!(ScoringRules::new([5, 2, 3], [1, 3, 10]), actual);
+    }
+
+    #[test]
+    fn unknown_entries_report_their_position() {
+        let toml = ScoringRules::parse_toml("[shapes]\nlizard = 4\n").unwrap_err();
+        let json = ScoringRules::parse_json("{\"shapes\": {\n\"rock\": x}}").unwrap_err();
+
+        assert!(matches!(toml, Error::Parse { line: 2, .. }));
+        assert!(matches!(json, Error::Parse { line: 2, column: 9, .. }));
+    }
+
+    #[test]
+    fn variants_name_their_own_shapes() {
+        // given
+        let rules = ScoringRules::for_variant(Variant::rpsls()).read_toml("[shapes]\nlizard = 10\n").unwrap();
+        let shape = |name| rules.variant().shape(name).unwrap();
+
+        // when
+        let actual = rules.score(&(shape("spock"), shape("lizard")).into()).unwrap();
+
+        // then
+        assert_eq!((4, 16), actual);
+    }
+
+    #[test]
+    fn shapes_outside_the_variant_are_rejected() {
+        let actual = ScoringRules::default().score(&(Shape(0), Shape(4)).into()).unwrap_err();
+
+        assert!(matches!(actual, Error::Validation(_)));
+    }
+}
@@ -0,0 +1,40 @@
+use aoc_common::{Result, Solution};
+use crate::{
+    file_games::{
+        self,
+        correct::OutcomeDecoder,
+        wrong::ShapeDecoder,
+    },
+    score_keeper::{Game, ScoreKeeper},
+};
+
+pub struct Day2;
+
+/// The strategy guide decoded both ways: second column as a move, and as an outcome.
+pub struct Guide {
+    wrong: Vec<Game>,
+    correct: Vec<Game>,
+}
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+
+    type Input = Guide;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let wrong = file_games::games_from_lines(&lines, &ShapeDecoder)?;
+        let correct = file_games::games_from_lines(&lines, &OutcomeDecoder)?;
+        Ok(Guide { wrong, correct })
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String> {
+        let (_, player) = ScoreKeeper::new(&input.wrong).total_first()?;
+        Ok(player.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String> {
+        let (_, player) = ScoreKeeper::new(&input.correct).total_first()?;
+        Ok(player.to_string())
+    }
+}
@@ -0,0 +1,430 @@
+use std::fmt;
+use aoc_common::{Error, Result};
+use crate::{
+    score_keeper::{score_game, Game, Outcome},
+    scoring::ScoringRules,
+    variant::{Shape, Variant},
+};
+
+/// One round as a bot saw it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub own: Shape,
+    pub other: Shape,
+}
+
+/// A strategy that picks each round's shape from the rounds played so far in its match.
+pub trait Bot {
+    fn play(&mut self, variant: &Variant, history: &[Round]) -> Shape;
+}
+
+/// The bots a tournament can field. Each match builds fresh bots, so nothing is learned between matches.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BotSpec {
+    /// The same shape every round.
+    Constant(Shape),
+    /// Every shape in turn, from the given one.
+    Cycle(Shape),
+    /// Uniformly random shapes from a seeded generator.
+    Random(u64),
+    /// Beats the opponent's most common shape so far.
+    Frequency,
+    /// Beats the shape the opponent has most often followed its last shape with.
+    Markov,
+    /// The player's shapes from a strategy guide, over and over.
+    Replay(Vec<Shape>),
+}
+
+impl BotSpec {
+    /// Reads `constant:<shape>`, `cycle[:<shape>]`, `random[:<seed>]`, `frequency`, `markov` or `guide`;
+    /// `guide` replays `guide`'s shapes.
+    pub fn parse(s: &str, variant: &Variant, guide: &[Shape]) -> Result<BotSpec, String> {
+        let (kind, argument) = match s.split_once(':') {
+            Some((kind, argument)) => (kind, Some(argument)),
+            None => (s, None),
+        };
+        let shape = |name: &str| variant.shape(name).ok_or(format!("not a shape in {}: {}", variant, name));
+        match (kind, argument) {
+            ("constant", Some(name)) => Ok(BotSpec::Constant(shape(name)?)),
+            ("cycle", None) => Ok(BotSpec::Cycle(Shape(0))),
+            ("cycle", Some(name)) => Ok(BotSpec::Cycle(shape(name)?)),
+            ("random", None) => Ok(BotSpec::Random(DEFAULT_SEED)),
+            ("random", Some(seed)) => Ok(BotSpec::Random(seed.parse().map_err(|_| format!("not a valid seed: {}", seed))?)),
+            ("frequency", None) => Ok(BotSpec::Frequency),
+            ("markov", None) => Ok(BotSpec::Markov),
+            ("guide", None) => Ok(BotSpec::Replay(guide.to_vec())),
+            _ => Err(format!("unknown bot: {}", s)),
+        }
+    }
+
+    /// One of each kind, the way [`parse`](BotSpec::parse) builds them without arguments.
+    pub fn all(guide: &[Shape]) -> Vec<BotSpec> {
+        vec![
+            BotSpec::Constant(Shape(0)),
+            BotSpec::Cycle(Shape(0)),
+            BotSpec::Random(DEFAULT_SEED),
+            BotSpec::Frequency,
+            BotSpec::Markov,
+            BotSpec::Replay(guide.to_vec()),
+        ]
+    }
+
+    pub fn build(&self) -> Box<dyn Bot> {
+        match self {
+            BotSpec::Constant(shape) => Box::new(Constant(*shape)),
+            BotSpec::Cycle(start) => Box::new(Cycle(*start)),
+            BotSpec::Random(seed) => Box::new(Random(SplitMix(*seed))),
+            BotSpec::Frequency => Box::new(Frequency::default()),
+            BotSpec::Markov => Box::new(Markov::default()),
+            BotSpec::Replay(shapes) => Box::new(Replay(shapes.clone())),
+        }
+    }
+
+    fn shapes(&self) -> &[Shape] {
+        match self {
+            BotSpec::Constant(shape) | BotSpec::Cycle(shape) => std::slice::from_ref(shape),
+            BotSpec::Replay(shapes) => shapes,
+            BotSpec::Random(_) | BotSpec::Frequency | BotSpec::Markov => &[],
+        }
+    }
+
+    fn name(&self, variant: &Variant) -> String {
+        match self {
+            BotSpec::Constant(shape) => format!("constant {}", variant.name(*shape)),
+            BotSpec::Cycle(start) => format!("cycle from {}", variant.name(*start)),
+            BotSpec::Random(seed) => format!("random ({})", seed),
+            BotSpec::Frequency => "frequency".to_string(),
+            BotSpec::Markov => "markov".to_string(),
+            BotSpec::Replay(_) => "guide".to_string(),
+        }
+    }
+}
+
+const DEFAULT_SEED: u64 = 2022;
+
+struct Constant(Shape);
+
+impl Bot for Constant {
+    fn play(&mut self, _: &Variant, _: &[Round]) -> Shape {
+        self.0
+    }
+}
+
+struct Cycle(Shape);
+
+impl Bot for Cycle {
+    fn play(&mut self, variant: &Variant, history: &[Round]) -> Shape {
+        Shape((self.0.0 + history.len()) % variant.len())
+    }
+}
+
+struct Random(SplitMix);
+
+impl Bot for Random {
+    fn play(&mut self, variant: &Variant, _: &[Round]) -> Shape {
+        Shape(self.0.below(variant.len()))
+    }
+}
+
+// Counts for the learning bots, kept up as the history grows, so a match costs time linear in its rounds.
+// `seen` is how much of the history is counted; a shorter history or another variant starts over.
+#[derive(Default)]
+struct Tally {
+    counts: Vec<u32>,
+    seen: usize,
+}
+
+impl Tally {
+    // The index of the first round of `history` not counted yet, after resetting to `len` zeroed counts if needed.
+    fn catch_up(&mut self, len: usize, history: &[Round]) -> usize {
+        if self.counts.len() != len || history.len() < self.seen {
+            *self = Tally { counts: vec![0; len], seen: 0 };
+        }
+        std::mem::replace(&mut self.seen, history.len())
+    }
+}
+
+#[derive(Default)]
+struct Frequency(Tally);
+
+impl Bot for Frequency {
+    fn play(&mut self, variant: &Variant, history: &[Round]) -> Shape {
+        let from = self.0.catch_up(variant.len(), history);
+        for round in &history[from..] {
+            self.0.counts[round.other.0] += 1;
+        }
+        variant.answer(most_common(&self.0.counts), Outcome::Win)
+    }
+}
+
+// Counts every transition between the opponent's consecutive shapes, row by the earlier shape.
+#[derive(Default)]
+struct Markov(Tally);
+
+impl Bot for Markov {
+    fn play(&mut self, variant: &Variant, history: &[Round]) -> Shape {
+        let n = variant.len();
+        let from = self.0.catch_up(n * n, history);
+        for index in from.max(1)..history.len() {
+            self.0.counts[history[index - 1].other.0 * n + history[index].other.0] += 1;
+        }
+        let Some(last) = history.last() else { return Shape(0) };
+        variant.answer(most_common(&self.0.counts[last.other.0 * n..(last.other.0 + 1) * n]), Outcome::Win)
+    }
+}
+
+struct Replay(Vec<Shape>);
+
+impl Bot for Replay {
+    fn play(&mut self, _: &Variant, history: &[Round]) -> Shape {
+        if self.0.is_empty() {
+            return Shape(0)
+        }
+        self.0[history.len() % self.0.len()]
+    }
+}
+
+// The first of the most counted shapes.
+fn most_common(counts: &[u32]) -> Shape {
+    let most = counts.iter().max().copied().unwrap_or(0);
+    Shape(counts.iter().position(|count| *count == most).unwrap_or(0))
+}
+
+// Steele, Lea and Flood's SplitMix64: small, fast, and the same sequence everywhere for a seed.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+/// Totals for one match, first bot first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MatchResult {
+    pub points: (u64, u64),
+    pub rounds_won: (usize, usize),
+}
+
+/// Plays `rounds` rounds between two bots, scoring each with [`score_game`] as if `first` were the opponent.
+/// A bot playing a shape from outside the rules' variant is a validation error, and points past 64 bits an overflow.
+pub fn play_match(first: &mut dyn Bot, second: &mut dyn Bot, rules: &ScoringRules, rounds: usize) -> Result<MatchResult> {
+    let variant = rules.variant();
+    let mut first_history = Vec::with_capacity(rounds);
+    let mut second_history = Vec::with_capacity(rounds);
+    let mut result = MatchResult { points: (0, 0), rounds_won: (0, 0) };
+    for _ in 0..rounds {
+        let a = first.play(variant, &first_history);
+        let b = second.play(variant, &second_history);
+        let (a_points, b_points) = score_game(&Game::from((a, b)), rules)?;
+        add_points(&mut result.points.0, a_points)?;
+        add_points(&mut result.points.1, b_points)?;
+        match variant.outcome(a, b) {
+            Outcome::Win => result.rounds_won.0 += 1,
+            Outcome::Loss => result.rounds_won.1 += 1,
+            Outcome::Draw => {}
+        }
+        first_history.push(Round { own: a, other: b });
+        second_history.push(Round { own: b, other: a });
+    }
+    Ok(result)
+}
+
+fn add_points(total: &mut u64, points: u64) -> Result<()> {
+    *total = total.checked_add(points).ok_or_else(|| Error::overflow("a bot's points do not fit in 64 bits"))?;
+    Ok(())
+}
+
+/// One bot's record; a match is won by scoring more points in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Standing {
+    pub bot: String,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub points: u64,
+    pub rounds_won: usize,
+}
+
+impl Standing {
+    /// Three for a match win and one for a draw.
+    pub fn league_points(&self) -> usize {
+        3 * self.wins + self.draws
+    }
+}
+
+/// Standings from best to worst: by league points, then points scored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tournament {
+    pub rounds: usize,
+    pub standings: Vec<Standing>,
+}
+
+/// Every bot plays every other bot once, over `rounds` rounds.
+pub fn round_robin(bots: &[BotSpec], rules: &ScoringRules, rounds: usize) -> Result<Tournament> {
+    if bots.len() < 2 {
+        return Err(Error::validation("a tournament needs at least two bots"))
+    }
+    let variant = rules.variant();
+    if let Some(shape) = bots.iter().flat_map(BotSpec::shapes).find(|shape| !variant.contains(**shape)) {
+        return Err(Error::validation(format!("shape {} is not in {}", shape.0, variant)))
+    }
+    let mut standings = bots
+        .iter()
+        .map(|bot| Standing { bot: bot.name(variant), wins: 0, draws: 0, losses: 0, points: 0, rounds_won: 0 })
+        .collect::<Vec<_>>();
+    for i in 0..bots.len() {
+        for j in i + 1..bots.len() {
+            let result = play_match(bots[i].build().as_mut(), bots[j].build().as_mut(), rules, rounds)?;
+            let (first, second) = (result.points.0, result.points.1);
+            add_points(&mut standings[i].points, first)?;
+            add_points(&mut standings[j].points, second)?;
+            standings[i].rounds_won += result.rounds_won.0;
+            standings[j].rounds_won += result.rounds_won.1;
+            match first.cmp(&second) {
+                std::cmp::Ordering::Greater => {
+                    standings[i].wins += 1;
+                    standings[j].losses += 1;
+                }
+                std::cmp::Ordering::Less => {
+                    standings[i].losses += 1;
+                    standings[j].wins += 1;
+                }
+                std::cmp::Ordering::Equal => {
+                    standings[i].draws += 1;
+                    standings[j].draws += 1;
+                }
+            }
+        }
+    }
+    standings.sort_by_key(|standing| std::cmp::Reverse((standing.league_points(), standing.points)));
+    Ok(Tournament { rounds, standings })
+}
+
+impl fmt::Display for Tournament {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.standings.iter().map(|standing| standing.bot.len()).max().unwrap_or(0).max(3);
+        writeln!(f, "{} rounds a match", self.rounds)?;
+        writeln!(f, "{:>4}  {:<width$}  {:>3} {:>3} {:>3}  {:>10}  {:>10}", "rank", "bot", "w", "d", "l", "points", "rounds won")?;
+        for (index, standing) in self.standings.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<width$}  {:>3} {:>3} {:>3}  {:>10}  {:>10}",
+                index + 1, standing.bot, standing.wins, standing.draws, standing.losses, standing.points, standing.rounds_won
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::score_keeper::Move::{Paper, Rock};
+
+    #[test]
+    fn frequency_beats_a_constant_bot() {
+        // given
+        let rules = ScoringRules::default();
+
+        // when
+        let actual = play_match(&mut Constant(Rock.into()), &mut Frequency::default(), &rules, 100).unwrap();
+
+        // then: with nothing seen it guesses rock too, so paper wins every round
+        assert_eq!((0, 100), actual.rounds_won);
+        assert_eq!((100, 800), actual.points);
+    }
+
+    #[test]
+    fn large_points_do_not_overflow() {
+        let rules = ScoringRules::new([1, 2, 3], [0, 3, 4_000_000_000]);
+
+        let actual = play_match(&mut Constant(Rock.into()), &mut Frequency::default(), &rules, 3).unwrap();
+
+        assert_eq!((3, 12_000_000_006), actual.points);
+    }
+
+    #[test]
+    fn markov_learns_a_cycle() {
+        let rules = ScoringRules::for_variant(Variant::rpsls());
+
+        let actual = play_match(&mut Cycle(Shape(0)), &mut Markov::default(), &rules, 100).unwrap();
+
+        // one lap of five to see every transition, then every round won
+        assert!(actual.rounds_won.1 >= 94, "{:?}", actual);
+    }
+
+    #[test]
+    fn learning_bots_follow_a_history_they_did_not_see_grow() {
+        // given: the opponent played rock, then paper twice
+        let variant = Variant::classic();
+        let history = [Rock, Paper, Paper].map(|other| Round { own: Shape(0), other: other.into() });
+
+        // when
+        let mut frequency = Frequency::default();
+        let mut markov = Markov::default();
+        frequency.play(&variant, &history[..1]);
+        markov.play(&variant, &history[..1]);
+
+        // then: both pick up the rounds they skipped, and start over on a new match
+        assert_eq!(Shape(2), frequency.play(&variant, &history));
+        assert_eq!(Shape(2), markov.play(&variant, &history));
+        assert_eq!(Shape(1), frequency.play(&variant, &history[..1]));
+        assert_eq!(Shape(1), markov.play(&variant, &history[..1]));
+    }
+
+    #[test]
+    fn random_bots_repeat_for_a_seed() {
+        let variant = Variant::classic();
+        let shapes = |seed| {
+            let mut bot = BotSpec::Random(seed).build();
+            (0..20).map(|_| bot.play(&variant, &[])).collect::<Vec<_>>()
+        };
+
+        assert_eq!(shapes(7), shapes(7));
+        assert_ne!(shapes(7), shapes(8));
+        assert!(Variant::classic().shapes().all(|shape| shapes(7).contains(&shape)));
+    }
+
+    #[test]
+    fn can_rank_a_round_robin() {
+        // given
+        let bots = vec![BotSpec::Constant(Rock.into()), BotSpec::Frequency, BotSpec::Replay(vec![Paper.into()])];
+
+        // when
+        let actual = round_robin(&bots, &ScoringRules::default(), 50).unwrap();
+
+        // then: frequency beats both constant bots, and paper beats rock
+        let order = actual.standings.iter().map(|standing| standing.bot.as_str()).collect::<Vec<_>>();
+        assert_eq!(vec!["frequency", "guide", "constant rock"], order);
+        assert_eq!((2, 0, 0), (actual.standings[0].wins, actual.standings[0].draws, actual.standings[0].losses));
+    }
+
+    #[test]
+    fn bots_outside_the_variant_are_rejected() {
+        let bots = vec![BotSpec::Constant(Shape(4)), BotSpec::Frequency];
+
+        let actual = round_robin(&bots, &ScoringRules::default(), 10).unwrap_err();
+
+        assert!(matches!(actual, Error::Validation(_)));
+    }
+
+    #[test]
+    fn can_parse_bots() {
+        let variant = Variant::rpsls();
+
+        assert_eq!(Ok(BotSpec::Constant(Shape(4))), BotSpec::parse("constant:lizard", &variant, &[]));
+        assert_eq!(Ok(BotSpec::Random(9)), BotSpec::parse("random:9", &variant, &[]));
+        assert_eq!(Ok(BotSpec::Replay(vec![Shape(1)])), BotSpec::parse("guide", &variant, &[Shape(1)]));
+        assert!(BotSpec::parse("constant", &variant, &[]).is_err());
+        assert!(BotSpec::parse("markov:2", &variant, &[]).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    fmt,
+    str::FromStr,
+};
+use aoc_common::{Error, Result};
+use crate::score_keeper::{Move, Outcome};
+
+/// One shape of a [`Variant`], by its 0-based place in the variant's order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+impl From<Move> for Shape {
+    fn from(shape: Move) -> Self {
+        Shape(shape as usize)
+    }
+}
+
+/// A cyclic game over an odd number of shapes, where each shape beats the ones an odd number of
+/// places before it, wrapping around. Every shape then beats exactly half of the others.
+///
+/// The classic game is rock, paper, scissors; its five-shape extension adds spock and lizard after them,
+/// so the first three shapes keep their puzzle meaning.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Variant {
+    names: Vec<String>,
+}
+
+impl Default for Variant {
+    fn default() -> Self {
+        Variant::classic()
+    }
+}
+
+impl Variant {
+    /// Needs an odd number of at least three distinct names, which are matched ignoring case.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Result<Self> {
+        let names = names.iter().map(|name| name.as_ref().trim().to_lowercase()).collect::<Vec<_>>();
+        if names.len() < 3 || names.len() % 2 == 0 {
+            return Err(Error::validation(format!("a cyclic game needs an odd number of at least 3 shapes, not {}", names.len())))
+        }
+        for (index, name) in names.iter().enumerate() {
+            if name.is_empty() || name.contains(|c: char| c.is_whitespace() || c == ',') {
+                return Err(Error::validation(format!("not a valid shape name: {:?}", name)))
+            }
+            if names[..index].contains(name) {
+                return Err(Error::validation(format!("shape named twice: {}", name)))
+            }
+        }
+        Ok(Variant { names })
+    }
+
+    pub fn classic() -> Self {
+        Variant { names: ["rock", "paper", "scissors"].map(String::from).to_vec() }
+    }
+
+    /// Rock, paper, scissors, spock, lizard.
+    pub fn rpsls() -> Self {
+        Variant { names: ["rock", "paper", "scissors", "spock", "lizard"].map(String::from).to_vec() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.names.len()).map(Shape)
+    }
+
+    /// Whether `shape` is one of this variant's; the methods taking shapes panic on any other.
+    pub fn contains(&self, shape: Shape) -> bool {
+        shape.0 < self.len()
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn shape(&self, name: &str) -> Option<Shape> {
+        self.names.iter().position(|known| known.eq_ignore_ascii_case(name)).map(Shape)
+    }
+
+    /// How a round ends for whoever plays `shape` against `other`.
+    pub fn outcome(&self, shape: Shape, other: Shape) -> Outcome {
+        match (shape.0 + self.len() - other.0) % self.len() {
+            0 => Outcome::Draw,
+            places if places % 2 == 1 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// The nearest shape that gets `outcome` against `other`: the one just after it to win, just before it to lose.
+    /// In the classic game that is the only such shape.
+    pub fn answer(&self, other: Shape, outcome: Outcome) -> Shape {
+        match outcome {
+            Outcome::Loss => Shape((other.0 + self.len() - 1) % self.len()),
+            Outcome::Draw => other,
+            Outcome::Win => Shape((other.0 + 1) % self.len()),
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = String;
+
+    /// `classic`, `rpsls`, or the shapes' names in order, separated by commas.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(Variant::classic()),
+            "rpsls" => Ok(Variant::rpsls()),
+            names => Variant::new(&names.split(',').collect::<Vec<_>>()).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_shape_beats_half_of_the_others() {
+        for variant in [Variant::classic(), Variant::rpsls(), "a,b,c,d,e,f,g".parse().unwrap()] {
+            for shape in variant.shapes() {
+                let wins = variant.shapes().filter(|other| variant.outcome(shape, *other) == Outcome::Win).count();
+                let losses = variant.shapes().filter(|other| variant.outcome(shape, *other) == Outcome::Loss).count();
+
+                assert_eq!((wins, losses), ((variant.len() - 1) / 2, (variant.len() - 1) / 2), "{}", variant.name(shape));
+            }
+        }
+    }
+
+    #[test]
+    fn rpsls_follows_the_usual_rules() {
+        // given
+        let variant = Variant::rpsls();
+        let shape = |name| variant.shape(name).unwrap();
+
+        // then
+        for (winner, loser) in [
+            ("scissors", "paper"), ("paper", "rock"), ("rock", "lizard"), ("lizard", "spock"), ("spock", "scissors"),
+            ("scissors", "lizard"), ("lizard", "paper"), ("paper", "spock"), ("spock", "rock"), ("rock", "scissors"),
+        ] {
+            assert_eq!(Outcome::Win, variant.outcome(shape(winner), shape(loser)), "{} beats {}", winner, loser);
+            assert_eq!(Outcome::Loss, variant.outcome(shape(loser), shape(winner)), "{} loses to {}", loser, winner);
+        }
+    }
+
+    #[test]
+    fn answers_get_the_asked_outcome() {
+        let variant = Variant::rpsls();
+
+        for other in variant.shapes() {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                assert_eq!(outcome, variant.outcome(variant.answer(other, outcome), other));
+            }
+        }
+    }
+
+    #[test]
+    fn even_or_repeated_shapes_are_rejected() {
+        assert!("rock,paper".parse::<Variant>().is_err());
+        assert!("a,b,c,d".parse::<Variant>().is_err());
+        assert!("a,b,A".parse::<Variant>().is_err());
+    }
+}
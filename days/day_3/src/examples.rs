@@ -0,0 +1,18 @@
+use aoc_common::{examples, Input, Part};
+use crate::{
+    rucksack_searcher::RucksackSearcher,
+    string_data::StringData,
+};
+
+#[test]
+fn examples_match_expected_answers() {
+    examples::check_examples(3, |path, part| {
+        let string_data = StringData::from_input(&Input::File(path.to_path_buf()))?;
+        let rucksack_searcher = RucksackSearcher::new(string_data);
+        let answer = match part {
+            Part::One => rucksack_searcher.get_sum_of_priorities()?,
+            Part::Two => rucksack_searcher.get_sum_of_group_priorities()?,
+        };
+        Ok(answer.to_string())
+    });
+}
@@ -0,0 +1,8 @@
+pub mod rucksack_searcher;
+pub mod string_data;
+pub mod solution;
+
+#[cfg(test)]
+mod examples;
+
+pub const DATA: &str = include_str!("../../../data/day_3.txt");
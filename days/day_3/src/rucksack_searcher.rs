@@ -1,4 +1,4 @@
-use crate::error::Result;
+use aoc_common::{Error, Result};
 use std::collections::HashMap;
 
 pub trait RuckSackData {
@@ -6,6 +6,16 @@ pub trait RuckSackData {
     fn get_groups(&self) -> Result<Vec<Group>>;
 }
 
+impl<D: RuckSackData + ?Sized> RuckSackData for &D {
+    fn get_rucksacks(&self) -> Result<Vec<RuckSack>> {
+        (**self).get_rucksacks()
+    }
+
+    fn get_groups(&self) -> Result<Vec<Group>> {
+        (**self).get_groups()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct RuckSack {
     compartment_one: String,
@@ -74,9 +84,9 @@ impl<D: RuckSackData> RucksackSearcher<D> {
             .into_iter()
             .map(Self::convert_to_priorities)
             .collect::<Option<Vec<u32>>>()
-            .ok_or("Invalid char; does not have priority".to_string())?
+            .ok_or_else(|| Error::validation("Invalid char; does not have priority"))?
             .iter()
-            .fold(0, |acc, priority| acc + priority);
+            .sum();
         Ok(sum)
     }
 
@@ -88,7 +98,7 @@ impl<D: RuckSackData> RucksackSearcher<D> {
             .map(Self::badge_from_group)
             .map(|item| item.and_then(Self::convert_to_priorities))
             .collect::<Option<Vec<u32>>>()
-            .ok_or("No priority found for a group!".to_string())?
+            .ok_or_else(|| Error::validation("No priority found for a group!"))?
             .iter()
             .sum();
         Ok(sum)
@@ -134,7 +144,7 @@ impl<D: RuckSackData> RucksackSearcher<D> {
                 shared
             })
             .collect::<Option<_>>()
-            .ok_or("One of the rucksacks didn't have a shared item".to_string())?;
+            .ok_or_else(|| Error::validation("One of the rucksacks didn't have a shared item"))?;
         Ok(shared)
     }
 
@@ -238,7 +248,7 @@ mod tests {
             let group = Group { one, two, three };
             Ok(group)
         } else {
-            Err("Groups are made from 3 rucksacks!".to_string())
+            Err(Error::validation("Groups are made from 3 rucksacks!"))
         }
     }
 
@@ -0,0 +1,46 @@
+use aoc_common::{Result, Solution};
+use crate::{
+    rucksack_searcher::{Group, RuckSack, RuckSackData, RucksackSearcher},
+    string_data::StringData,
+};
+
+pub struct Day3;
+
+/// Rucksacks and groups read up front, so solving doesn't re-split the input.
+pub struct Rucksacks {
+    rucksacks: Vec<RuckSack>,
+    groups: Vec<Group>,
+}
+
+impl RuckSackData for Rucksacks {
+    fn get_rucksacks(&self) -> Result<Vec<RuckSack>> {
+        Ok(self.rucksacks.clone())
+    }
+
+    fn get_groups(&self) -> Result<Vec<Group>> {
+        Ok(self.groups.clone())
+    }
+}
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    type Input = Rucksacks;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let string_data = StringData::new(input);
+        let rucksacks = string_data.get_rucksacks()?;
+        let groups = string_data.get_groups()?;
+        Ok(Rucksacks { rucksacks, groups })
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String> {
+        let sum_of_priorities = RucksackSearcher::new(input).get_sum_of_priorities()?;
+        Ok(sum_of_priorities.to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String> {
+        let sum_of_groups = RucksackSearcher::new(input).get_sum_of_group_priorities()?;
+        Ok(sum_of_groups.to_string())
+    }
+}
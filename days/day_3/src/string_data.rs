@@ -1,4 +1,4 @@
-use crate::error::Result;
+use aoc_common::{Error, Input, Result};
 use crate::rucksack_searcher::{Group, RuckSack, RuckSackData};
 
 pub struct StringData {
@@ -12,26 +12,41 @@ impl StringData {
         }
     }
 
-    fn rucksack_from_line(line: &str) -> Result<RuckSack> {
+    pub fn from_input(input: &Input) -> Result<Self> {
+        let inner = input.read_to_string()?;
+        Ok(StringData { inner })
+    }
+
+    fn rucksack_from_line(line_number: usize, line: &str) -> Result<RuckSack> {
         let len = line.len();
-        if len % 2 == 0 {
+        if len.is_multiple_of(2) {
             let half = len / 2;
             let one = &line[..half];
             let two = &line[half..];
             let rucksack = RuckSack::new(one, two);
             Ok(rucksack)
         } else {
-            Err("Odd amount in rucksack compartment!".to_string())
+            Err(Error::parse(line_number, 1, "Odd amount in rucksack compartment!"))
         }
     }
 }
 
 impl RuckSackData for StringData {
     fn get_rucksacks(&self) -> Result<Vec<RuckSack>> {
-        self.inner.lines().map(Self::rucksack_from_line).collect()
+        if self.inner.trim().is_empty() {
+            return Err(Error::EmptyInput)
+        }
+        self.inner
+            .lines()
+            .enumerate()
+            .map(|(index, line)| Self::rucksack_from_line(index + 1, line))
+            .collect()
     }
 
     fn get_groups(&self) -> Result<Vec<Group>> {
+        if self.inner.trim().is_empty() {
+            return Err(Error::EmptyInput)
+        }
         self.inner
             .lines()
             .collect::<Vec<_>>()
@@ -49,7 +64,7 @@ fn group_from_three_chunk(chunk: &[&str]) -> Result<Group> {
         let group = Group::new(one, two, three);
         Ok(group)
     } else {
-        Err("Groups are made from 3 rucksacks!".to_string())
+        Err(Error::validation("Groups are made from 3 rucksacks!"))
     }
 }
 
@@ -87,4 +102,15 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn odd_rucksack_reports_its_line() {
+        let data = "aa\nbab\n";
+
+        let string_data = StringData::new(data);
+
+        let actual = string_data.get_rucksacks().unwrap_err();
+
+        assert!(matches!(actual, Error::Parse { line: 2, .. }));
+    }
 }